@@ -0,0 +1,109 @@
+//! Runtime-agnostic primitives behind the `async` feature.
+//!
+//! The worker thread stays synchronous; these types let it wake tasks that are
+//! waiting for channel capacity or for an acknowledgement, without parking an
+//! executor thread.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// A one-shot completion flag that a task can await, optionally carrying a value.
+#[derive(Debug)]
+pub(crate) struct Signal<T = ()> {
+    state: Mutex<SignalState<T>>,
+}
+
+#[derive(Debug)]
+struct SignalState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+impl<T: Clone> Signal<T> {
+    pub(crate) fn new() -> Arc<Self> {
+        Arc::new(Signal {
+            state: Mutex::new(SignalState {
+                value: None,
+                waker: None,
+            }),
+        })
+    }
+
+    /// Marks the signal as completed with `value` and wakes the waiting task, if any.
+    pub(crate) fn complete(&self, value: T) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.value = Some(value);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    pub(crate) fn wait(self: &Arc<Self>) -> SignalFuture<T> {
+        SignalFuture {
+            signal: Arc::clone(self),
+        }
+    }
+}
+
+pub(crate) struct SignalFuture<T> {
+    signal: Arc<Signal<T>>,
+}
+
+impl<T: Clone> Future for SignalFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.signal.state.lock().unwrap();
+        match &state.value {
+            Some(value) => Poll::Ready(value.clone()),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Tasks waiting for room in the logger's channel.
+///
+/// The worker calls [`notify`](Self::notify) after every message it takes off
+/// the channel; the flag keeps that a single atomic load when nobody waits.
+#[derive(Debug, Default)]
+pub(crate) struct CapacityWaiters {
+    has_waiters: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CapacityWaiters {
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+        self.has_waiters.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn notify(&self) {
+        if !self.has_waiters.load(Ordering::SeqCst) {
+            return;
+        }
+        let wakers = {
+            let mut wakers = self.wakers.lock().unwrap();
+            self.has_waiters.store(false, Ordering::SeqCst);
+            std::mem::take(&mut *wakers)
+        };
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    profiler::{self, Profiler},
+    Logger,
+};
+use logform::LogInfo;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A lightweight handle derived from a [`Logger`] that stamps a fixed set of
+/// metadata onto every entry it logs.
+///
+/// A child shares its parent's worker thread, transports and levels; creating
+/// one only copies the metadata fields. Per-entry metadata wins over the
+/// child's fields when both define the same key.
+///
+/// The child owns its handle to the parent, so it can be moved into a spawned
+/// thread or task. Dropping a child never closes the parent, and entries logged
+/// after the parent has been closed or dropped are discarded.
+///
+/// # Example
+/// ```rust
+/// use winston::{log, Logger};
+///
+/// let logger = Logger::new(None);
+/// let request_logger = logger.child([("request_id", "abc-123")]);
+///
+/// log!(request_logger, info, "Handling request");
+/// ```
+#[derive(Debug)]
+pub struct ChildLogger {
+    parent: Logger,
+    meta: HashMap<String, Value>,
+}
+
+impl ChildLogger {
+    pub(crate) fn new(parent: Logger, meta: HashMap<String, Value>) -> Self {
+        ChildLogger { parent, meta }
+    }
+
+    /// Creates a nested child that carries this child's metadata merged with `meta`.
+    ///
+    /// Fields in `meta` override fields with the same key inherited from this child.
+    pub fn child<I, K, V>(&self, meta: I) -> ChildLogger
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        let mut merged = self.meta.clone();
+        merged.extend(meta.into_iter().map(|(k, v)| (k.into(), v.into())));
+        ChildLogger::new(self.parent.handle(), merged)
+    }
+
+    /// Logs an entry through the parent logger after adding the child's metadata.
+    pub fn log(&self, mut entry: LogInfo) {
+        for (key, value) in &self.meta {
+            entry
+                .meta
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self.parent.log(entry);
+    }
+
+    /// Starts a timer that logs through this child, with its metadata, when
+    /// finished or dropped. See [`Logger::start_timer`].
+    pub fn start_timer(&self) -> Profiler {
+        Profiler::new(self.clone())
+    }
+
+    /// Starts or stops the profile named `id`, logging through this child when
+    /// it stops. See [`Logger::profile`].
+    ///
+    /// Profiles are shared with the parent and its other children, so a
+    /// profile can be started through one and stopped through another.
+    pub fn profile(&self, id: impl Into<String>) {
+        let id = id.into();
+        let entry = LogInfo::new("info", id.clone());
+        self.profile_with(id, entry);
+    }
+
+    /// Like [`profile`](Self::profile), but logs `entry` when the profile stops.
+    pub fn profile_with(&self, id: impl Into<String>, entry: LogInfo) {
+        if let Some(elapsed) = self.parent.toggle_profile(id.into()) {
+            self.log(profiler::with_duration(entry, elapsed));
+        }
+    }
+
+    /// Returns `true` if the parent logger would accept an entry at `level`.
+    pub fn is_enabled(&self, level: &str) -> bool {
+        self.parent.is_enabled(level)
+    }
+
+    /// Returns the metadata this child adds to every entry.
+    pub fn meta(&self) -> &HashMap<String, Value> {
+        &self.meta
+    }
+
+    /// Returns the logger this child writes through.
+    pub fn parent(&self) -> &Logger {
+        &self.parent
+    }
+}
+
+impl Clone for ChildLogger {
+    fn clone(&self) -> Self {
+        ChildLogger::new(self.parent.handle(), self.meta.clone())
+    }
+}
@@ -0,0 +1,632 @@
+use crate::{
+    logger_levels::LoggerLevels,
+    logger_options::{BackpressureStrategy, LoggerOptions},
+    logger_transport::LoggerTransport,
+    transports::{self, File},
+};
+use logform::{Format, LogInfo};
+use serde_json::{Map, Value};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt,
+    fs::OpenOptions,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Builds a transport from its entry in a configuration file.
+pub type TransportFactory =
+    Arc<dyn Fn(&TransportConfig) -> Result<LoggerTransport<LogInfo>, String> + Send + Sync>;
+
+/// The syntax of a configuration source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    /// Picks the format from a file's extension: `.json` is JSON, `.toml` is TOML.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Loads [`LoggerOptions`] from a TOML or JSON description, so log
+/// destinations can change without recompiling.
+///
+/// The recognised keys are `level`, `levels`, `format`, `channel_capacity`,
+/// `backpressure_strategy` and `transports`. Each transport names a factory
+/// with `type`; `stdout`, `stderr` and `file` are built in and others can be
+/// added with [`register_transport`](Self::register_transport). Errors name
+/// the file and the offending key, e.g. `app.toml: transports[1].type: unknown
+/// transport "kafka"`.
+///
+/// `level` and each transport's `level` must name one of the configured
+/// levels: those in the file's `levels` table, or the defaults. A transport
+/// entry may only hold `type`, `level`, `format` and the keys its factory
+/// reads.
+///
+/// # Example
+/// ```ignore
+/// // level = "debug"
+/// // format = ["timestamp", "json"]
+/// // backpressure_strategy = { type = "block_timeout", timeout_ms = 5 }
+/// //
+/// // [[transports]]
+/// // type = "file"
+/// // path = "app.log"
+/// // level = "warn"
+/// let options = ConfigLoader::new()
+///     .register_transport("syslog", |config| {
+///         let host = config.require_str("host")?;
+///         Ok(LoggerTransport::new(SyslogTransport::connect(host).map_err(|e| config.error("host", e))?))
+///     })
+///     .load_file("logging.toml")?;
+/// ```
+#[derive(Clone)]
+pub struct ConfigLoader {
+    factories: HashMap<String, TransportFactory>,
+}
+
+/// A transport's entry in a configuration file, handed to its [`TransportFactory`].
+///
+/// Keys the factory never looks up are reported as unknown, so a misspelt
+/// option fails the load instead of being ignored.
+pub struct TransportConfig<'a> {
+    key: String,
+    table: &'a Map<String, Value>,
+    read: RefCell<HashSet<String>>,
+}
+
+impl TransportConfig<'_> {
+    /// The entry's position in the file, e.g. `transports[0]`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the raw value of `name`, if present.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.read.borrow_mut().insert(name.to_string());
+        self.table.get(name)
+    }
+
+    /// Returns the string value of `name`, if present.
+    pub fn str(&self, name: &str) -> Result<Option<&str>, String> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .as_str()
+                    .ok_or_else(|| self.error(name, "expected a string"))
+            })
+            .transpose()
+    }
+
+    /// Returns the string value of `name`, which must be present.
+    pub fn require_str(&self, name: &str) -> Result<&str, String> {
+        self.str(name)?
+            .ok_or_else(|| self.error(name, "missing required key"))
+    }
+
+    /// Returns the unsigned integer value of `name`, if present.
+    pub fn u64(&self, name: &str) -> Result<Option<u64>, String> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .as_u64()
+                    .ok_or_else(|| self.error(name, "expected a non-negative integer"))
+            })
+            .transpose()
+    }
+
+    /// Returns the boolean value of `name`, if present.
+    pub fn bool(&self, name: &str) -> Result<Option<bool>, String> {
+        self.get(name)
+            .map(|value| {
+                value
+                    .as_bool()
+                    .ok_or_else(|| self.error(name, "expected a boolean"))
+            })
+            .transpose()
+    }
+
+    /// Formats an error about the key `name` in this entry.
+    pub fn error(&self, name: &str, message: impl fmt::Display) -> String {
+        format!("{}.{}: {}", self.key, name, message)
+    }
+
+    /// Fails on the first key, in file order, that nothing looked up.
+    fn reject_unread(&self) -> Result<(), String> {
+        let read = self.read.borrow();
+        match self.table.keys().find(|name| !read.contains(*name)) {
+            Some(name) => Err(self.error(name, "unknown key")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ConfigLoader {
+    /// Creates a loader with the built-in `stdout`, `stderr` and `file` transports.
+    pub fn new() -> Self {
+        ConfigLoader {
+            factories: HashMap::new(),
+        }
+        .register_transport("stdout", |_| Ok(LoggerTransport::new(transports::stdout())))
+        .register_transport("stderr", |_| Ok(LoggerTransport::new(transports::stderr())))
+        .register_transport("file", file_transport)
+    }
+
+    /// Makes `factory` available to transport entries with `type = name`,
+    /// replacing any factory already registered under that name.
+    ///
+    /// The loader applies the entry's `level` and `format` keys to the
+    /// transport the factory returns.
+    pub fn register_transport<F>(mut self, name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&TransportConfig) -> Result<LoggerTransport<LogInfo>, String> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    /// Reads and parses the file at `path`, choosing TOML or JSON by its extension.
+    pub fn load_file(&self, path: impl AsRef<Path>) -> Result<LoggerOptions, String> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path).ok_or_else(|| {
+            format!(
+                "{}: unsupported config format, expected a .toml or .json file",
+                path.display()
+            )
+        })?;
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| format!("{}: failed to read config: {}", path.display(), e))?;
+
+        self.load_str(&source, format)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parses a configuration held in memory.
+    pub fn load_str(&self, source: &str, format: ConfigFormat) -> Result<LoggerOptions, String> {
+        let table = parse_table(source, format)?;
+        self.build_options(&table, LoggerOptions::default())
+    }
+
+    fn build_options(
+        &self,
+        table: &Map<String, Value>,
+        mut options: LoggerOptions,
+    ) -> Result<LoggerOptions, String> {
+        for (key, value) in table {
+            match key.as_str() {
+                "level" => options.level = Some(expect_str(key, value)?.to_string()),
+                "levels" => options = options.levels(parse_levels(key, value)?),
+                "format" => options.format = Some(Arc::new(parse_format(key, value)?)),
+                "channel_capacity" => options.channel_capacity = Some(expect_usize(key, value)?),
+                "backpressure_strategy" => {
+                    options.backpressure_strategy = Some(parse_backpressure(key, value)?)
+                }
+                "transports" => {
+                    let entries = value
+                        .as_array()
+                        .ok_or_else(|| format!("{}: expected an array of tables", key))?;
+                    let transports = entries
+                        .iter()
+                        .enumerate()
+                        .map(|(i, entry)| self.build_transport(&format!("{}[{}]", key, i), entry))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    options = options.transports(transports);
+                }
+                _ => return Err(format!("{}: unknown key", key)),
+            }
+        }
+
+        // `levels` may come after `level`, so the level names are checked once
+        // everything is read. Without `levels`, a reload checks them against
+        // the logger's own.
+        if let Some(levels) = &options.levels {
+            check_levels(&options, levels)?;
+        }
+        Ok(options)
+    }
+
+    fn build_transport(
+        &self,
+        key: &str,
+        entry: &Value,
+    ) -> Result<LoggerTransport<LogInfo>, String> {
+        let table = entry
+            .as_object()
+            .ok_or_else(|| format!("{}: expected a table", key))?;
+        let config = TransportConfig {
+            key: key.to_string(),
+            table,
+            read: RefCell::default(),
+        };
+
+        let kind = config.require_str("type")?;
+        let factory = self.factories.get(kind).ok_or_else(|| {
+            let mut known: Vec<_> = self.factories.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            config.error(
+                "type",
+                format!(
+                    "unknown transport {:?} (registered: {})",
+                    kind,
+                    known.join(", ")
+                ),
+            )
+        })?;
+
+        let mut transport = factory(&config)?;
+        if let Some(level) = config.str("level")? {
+            transport = transport.with_level(level);
+        }
+        if let Some(format) = config.get("format") {
+            transport = transport.with_format(parse_format(&format!("{}.format", key), format)?);
+        }
+        config.reject_unread()?;
+        Ok(transport)
+    }
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ConfigLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut transports: Vec<_> = self.factories.keys().collect();
+        transports.sort_unstable();
+        f.debug_struct("ConfigLoader")
+            .field("transports", &transports)
+            .finish()
+    }
+}
+
+/// What changed in a watched config file since the version last applied.
+#[derive(Debug)]
+pub(crate) struct ConfigChange {
+    /// Options for the keys whose value changed, leaving the rest unset.
+    pub(crate) options: LoggerOptions,
+    /// Keys no longer in the file; the logger keeps their current value.
+    pub(crate) removed: Vec<String>,
+}
+
+/// A config file the worker polls for changes, set up by
+/// [`Logger::watch_config`](crate::Logger::watch_config).
+#[derive(Debug)]
+pub(crate) struct ConfigWatch {
+    path: PathBuf,
+    format: ConfigFormat,
+    loader: ConfigLoader,
+    interval: Duration,
+    next_check: Instant,
+    // What the last check saw: the file's contents, or why it couldn't be read
+    last_seen: Result<String, String>,
+    // The top-level keys as last applied, which later versions are compared to
+    applied: Map<String, Value>,
+}
+
+impl ConfigWatch {
+    /// Starts watching `path`, which must hold a valid configuration.
+    pub(crate) fn new(
+        path: PathBuf,
+        loader: ConfigLoader,
+        interval: Duration,
+    ) -> Result<Self, String> {
+        let format = ConfigFormat::from_path(&path).ok_or_else(|| {
+            format!(
+                "{}: unsupported config format, expected a .toml or .json file",
+                path.display()
+            )
+        })?;
+        let mut watch = ConfigWatch {
+            path,
+            format,
+            loader,
+            interval,
+            next_check: Instant::now() + interval,
+            last_seen: Err(String::new()),
+            applied: Map::new(),
+        };
+        let source = watch.read()?;
+        let table = watch.parse(&source)?;
+        watch.build(&table)?;
+        watch.last_seen = Ok(source);
+        watch.applied = table;
+        Ok(watch)
+    }
+
+    /// How long until the file is due to be checked again.
+    pub(crate) fn until_due(&self) -> Duration {
+        self.next_check.saturating_duration_since(Instant::now())
+    }
+
+    /// Checks the file if it is due, returning the keys that changed or were
+    /// removed since the last applied version, or why they couldn't be
+    /// loaded. Returns `None` if nothing has changed.
+    ///
+    /// `check` validates the changes against the logger before they count as
+    /// applied; if it fails, later versions are still compared to the last
+    /// version that was applied.
+    pub(crate) fn poll(
+        &mut self,
+        check: impl FnOnce(&LoggerOptions) -> Result<(), String>,
+    ) -> Option<Result<ConfigChange, String>> {
+        let now = Instant::now();
+        if now < self.next_check {
+            return None;
+        }
+        self.next_check = now + self.interval;
+
+        let seen = self.read();
+        if seen == self.last_seen {
+            return None;
+        }
+        self.last_seen = seen.clone();
+        let source = match seen {
+            Ok(source) => source,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let table = match self.parse(&source) {
+            Ok(table) => table,
+            Err(e) => return Some(Err(e)),
+        };
+        let removed: Vec<String> = self
+            .applied
+            .keys()
+            .filter(|key| !table.contains_key(*key))
+            .cloned()
+            .collect();
+        let changes: Map<String, Value> = table
+            .into_iter()
+            .filter(|(key, value)| self.applied.get(key) != Some(value))
+            .collect();
+        if changes.is_empty() && removed.is_empty() {
+            return None;
+        }
+
+        let options = self.build(&changes).and_then(|options| {
+            check(&options).map_err(|e| format!("{}: {}", self.path.display(), e))?;
+            Ok(options)
+        });
+        Some(options.map(|options| {
+            // Reported once; a key added back is applied again even if unchanged
+            for key in &removed {
+                self.applied.remove(key);
+            }
+            self.applied.extend(changes);
+            ConfigChange { options, removed }
+        }))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read(&self) -> Result<String, String> {
+        std::fs::read_to_string(&self.path)
+            .map_err(|e| format!("{}: failed to read config: {}", self.path.display(), e))
+    }
+
+    fn parse(&self, source: &str) -> Result<Map<String, Value>, String> {
+        parse_table(source, self.format).map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+
+    /// Builds options from `table`, leaving every option it doesn't mention unset.
+    fn build(&self, table: &Map<String, Value>) -> Result<LoggerOptions, String> {
+        self.loader
+            .build_options(table, LoggerOptions::unset())
+            .map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+}
+
+fn parse_table(source: &str, format: ConfigFormat) -> Result<Map<String, Value>, String> {
+    let root: Value = match format {
+        ConfigFormat::Toml => toml::from_str(source).map_err(|e| e.to_string())?,
+        ConfigFormat::Json => serde_json::from_str(source).map_err(|e| e.to_string())?,
+    };
+    match root {
+        Value::Object(table) => Ok(table),
+        _ => Err("expected a table at the top level".to_string()),
+    }
+}
+
+/// Checks that the logger's `level` and every transport's `level` are in `levels`.
+pub(crate) fn check_levels(options: &LoggerOptions, levels: &LoggerLevels) -> Result<(), String> {
+    let check = |key: &str, level: &str| match levels.get_severity(level) {
+        Some(_) => Ok(()),
+        None => Err(format!("{}: unknown level {:?}", key, level)),
+    };
+
+    if let Some(level) = &options.level {
+        check("level", level)?;
+    }
+    for (i, (_, transport)) in options.transports.iter().flatten().enumerate() {
+        if let Some(level) = transport.get_level() {
+            check(&format!("transports[{}].level", i), level)?;
+        }
+    }
+    Ok(())
+}
+
+fn file_transport(config: &TransportConfig) -> Result<LoggerTransport<LogInfo>, String> {
+    let path = PathBuf::from(config.require_str("path")?);
+    // Open it here first, since the file transport panics if it can't
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| config.error("path", format!("cannot open {}: {}", path.display(), e)))?;
+
+    Ok(LoggerTransport::new(File::builder().filename(path).build()))
+}
+
+/// Applies a list of formats in order.
+struct FormatChain(Vec<Box<dyn Format<Input = LogInfo> + Send + Sync>>);
+
+impl Format for FormatChain {
+    type Input = LogInfo;
+
+    fn transform(&self, input: LogInfo) -> Option<LogInfo> {
+        self.0
+            .iter()
+            .try_fold(input, |info, format| format.transform(info))
+    }
+}
+
+/// Parses a format name, a table with `type` and its options, or an array of either.
+fn parse_format(key: &str, value: &Value) -> Result<FormatChain, String> {
+    let formats = match value {
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| parse_single_format(&format!("{}[{}]", key, i), item))
+            .collect::<Result<_, _>>()?,
+        single => vec![parse_single_format(key, single)?],
+    };
+    Ok(FormatChain(formats))
+}
+
+fn parse_single_format(
+    key: &str,
+    value: &Value,
+) -> Result<Box<dyn Format<Input = LogInfo> + Send + Sync>, String> {
+    let (name, table) = match value {
+        Value::String(name) => (name.as_str(), None),
+        Value::Object(table) => {
+            let name = table
+                .get("type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("{}.type: missing required key", key))?;
+            (name, Some(table))
+        }
+        _ => return Err(format!("{}: expected a format name or table", key)),
+    };
+    let option = |option: &str| -> Result<Option<&str>, String> {
+        table
+            .and_then(|table| table.get(option))
+            .map(|value| {
+                value
+                    .as_str()
+                    .ok_or_else(|| format!("{}.{}: expected a string", key, option))
+            })
+            .transpose()
+    };
+
+    Ok(match name {
+        "align" => Box::new(logform::align()),
+        "cli" => Box::new(logform::cli()),
+        "colorize" => Box::new(logform::colorize()),
+        "json" => Box::new(logform::json()),
+        "label" => {
+            let label =
+                option("label")?.ok_or_else(|| format!("{}.label: missing required key", key))?;
+            Box::new(logform::label().with_label(label))
+        }
+        "logstash" => Box::new(logform::logstash()),
+        "metadata" => Box::new(logform::metadata()),
+        "ms" => Box::new(logform::ms()),
+        "pad_levels" => Box::new(logform::pad_levels()),
+        "passthrough" => Box::new(logform::passthrough()),
+        "pretty_print" => Box::new(logform::pretty_print()),
+        "simple" => Box::new(logform::simple()),
+        "timestamp" => match option("format")? {
+            Some(format) => Box::new(logform::timestamp().with_format(format)),
+            None => Box::new(logform::timestamp()),
+        },
+        "uncolorize" => Box::new(logform::uncolorize()),
+        other => return Err(format!("{}: unknown format {:?}", key, other)),
+    })
+}
+
+fn parse_levels(key: &str, value: &Value) -> Result<HashMap<String, u8>, String> {
+    let table = value
+        .as_object()
+        .ok_or_else(|| format!("{}: expected a table of level names to severities", key))?;
+    table
+        .iter()
+        .map(|(level, severity)| {
+            severity
+                .as_u64()
+                .and_then(|severity| u8::try_from(severity).ok())
+                .map(|severity| (level.clone(), severity))
+                .ok_or_else(|| format!("{}.{}: expected a severity from 0 to 255", key, level))
+        })
+        .collect()
+}
+
+/// Parses `"block"`, `"drop_oldest"` or `"drop_current"`, or a table such as
+/// `{ type = "block_timeout", timeout_ms = 5 }`.
+fn parse_backpressure(key: &str, value: &Value) -> Result<BackpressureStrategy, String> {
+    let (name, table) = match value {
+        Value::String(name) => (name.as_str(), None),
+        Value::Object(table) => {
+            let name = table
+                .get("type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("{}.type: missing required key", key))?;
+            (name, Some(table))
+        }
+        _ => return Err(format!("{}: expected a strategy name or table", key)),
+    };
+    let option = |option: &str| {
+        table
+            .and_then(|table| table.get(option))
+            .ok_or_else(|| format!("{}.{}: missing required key", key, option))
+    };
+
+    match name {
+        "block" => Ok(BackpressureStrategy::Block),
+        "drop_oldest" => Ok(BackpressureStrategy::DropOldest),
+        "drop_current" => Ok(BackpressureStrategy::DropCurrent),
+        "block_timeout" => {
+            let key = format!("{}.timeout_ms", key);
+            let millis = expect_u64(&key, option("timeout_ms")?)?;
+            Ok(BackpressureStrategy::BlockTimeout(Duration::from_millis(
+                millis,
+            )))
+        }
+        "sample" => {
+            let key = format!("{}.every", key);
+            let every = expect_u64(&key, option("every")?)?;
+            u32::try_from(every)
+                .map(BackpressureStrategy::Sample)
+                .map_err(|_| format!("{}: value is too large", key))
+        }
+        "spill_to_file" => {
+            let key = format!("{}.path", key);
+            let path = expect_str(&key, option("path")?)?;
+            Ok(BackpressureStrategy::SpillToFile(path.into()))
+        }
+        other => Err(format!(
+            "{}: unknown backpressure strategy {:?}",
+            key, other
+        )),
+    }
+}
+
+fn expect_str<'a>(key: &str, value: &'a Value) -> Result<&'a str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("{}: expected a string", key))
+}
+
+fn expect_u64(key: &str, value: &Value) -> Result<u64, String> {
+    value
+        .as_u64()
+        .ok_or_else(|| format!("{}: expected a non-negative integer", key))
+}
+
+fn expect_usize(key: &str, value: &Value) -> Result<usize, String> {
+    usize::try_from(expect_u64(key, value)?).map_err(|_| format!("{}: value is too large", key))
+}
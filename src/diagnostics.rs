@@ -0,0 +1,241 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+// The throttle of the default sink: at most this many diagnostics of each kind per window
+const DEFAULT_MAX_PER_WINDOW: u32 = 10;
+const DEFAULT_WINDOW: Duration = Duration::from_secs(10);
+
+/// What a diagnostic is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// An entry was discarded by backpressure.
+    Drop,
+    /// An entry was buffered because no transport is configured.
+    NoTransport,
+    /// The worker or a transport queue is gone, so an entry could not be sent.
+    Disconnected,
+    /// A transport failed to flush where no caller could receive the error.
+    FlushError,
+    /// A watched config file could not be reloaded, so the current configuration was kept.
+    Config,
+    /// Any other internal problem, such as a worker thread that panicked.
+    Internal,
+}
+
+/// How serious a diagnostic is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+impl DiagnosticKind {
+    fn severity(self) -> DiagnosticSeverity {
+        match self {
+            DiagnosticKind::Drop | DiagnosticKind::NoTransport => DiagnosticSeverity::Warning,
+            DiagnosticKind::Disconnected
+            | DiagnosticKind::FlushError
+            | DiagnosticKind::Config
+            | DiagnosticKind::Internal => DiagnosticSeverity::Error,
+        }
+    }
+}
+
+/// A problem the logger hit while handling entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Diagnostics of the same kind dropped by rate limiting since the last one reported.
+    pub suppressed: u64,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[winston] {}", self.message)?;
+        if self.suppressed > 0 {
+            write!(f, " ({} similar messages suppressed)", self.suppressed)?;
+        }
+        Ok(())
+    }
+}
+
+type DiagnosticCallback = Arc<dyn Fn(&Diagnostic) + Send + Sync>;
+
+#[derive(Clone)]
+enum Sink {
+    Stderr,
+    Callback(DiagnosticCallback),
+    Silent,
+}
+
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    reported: u32,
+    suppressed: u64,
+}
+
+/// Where the logger reports its own problems, such as dropped entries or
+/// failed flushes.
+///
+/// Without a sink configured, diagnostics go to stderr, at most 10 of each
+/// kind every 10 seconds. Clones share their rate-limiting state.
+///
+/// # Example
+/// ```ignore
+/// let logger = Logger::builder()
+///     .diagnostics(
+///         Diagnostics::stderr()
+///             .with_rate_limit(5, Duration::from_secs(10))
+///             .with_min_severity(DiagnosticSeverity::Error),
+///     )
+///     .build();
+/// ```
+#[derive(Clone)]
+pub struct Diagnostics {
+    sink: Sink,
+    min_severity: DiagnosticSeverity,
+    rate_limit: Option<(u32, Duration)>,
+    windows: Arc<Mutex<HashMap<DiagnosticKind, Window>>>,
+}
+
+impl Diagnostics {
+    fn with_sink(sink: Sink) -> Self {
+        Diagnostics {
+            sink,
+            min_severity: DiagnosticSeverity::Warning,
+            rate_limit: None,
+            windows: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Writes diagnostics to stderr.
+    pub fn stderr() -> Self {
+        Self::with_sink(Sink::Stderr)
+    }
+
+    /// Passes each diagnostic to `callback`, e.g. to count them in a metrics system.
+    ///
+    /// The callback may run on the logging thread or the worker thread. It must
+    /// not log through the same logger.
+    pub fn callback<F>(callback: F) -> Self
+    where
+        F: Fn(&Diagnostic) + Send + Sync + 'static,
+    {
+        Self::with_sink(Sink::Callback(Arc::new(callback)))
+    }
+
+    /// Discards all diagnostics.
+    pub fn silent() -> Self {
+        Self::with_sink(Sink::Silent)
+    }
+
+    /// Reports at most `max` diagnostics of each kind per `window`.
+    ///
+    /// The next diagnostic reported after a quiet period carries the number
+    /// that were suppressed.
+    pub fn with_rate_limit(mut self, max: u32, window: Duration) -> Self {
+        self.rate_limit = Some((max, window));
+        self
+    }
+
+    /// Ignores diagnostics below `severity`.
+    pub fn with_min_severity(mut self, severity: DiagnosticSeverity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Reports a diagnostic; `message` is only built if it will be reported.
+    pub(crate) fn report(&self, kind: DiagnosticKind, message: impl FnOnce() -> String) {
+        let severity = kind.severity();
+        if matches!(self.sink, Sink::Silent) || severity < self.min_severity {
+            return;
+        }
+        let Some(suppressed) = self.admit(kind) else {
+            return;
+        };
+
+        let diagnostic = Diagnostic {
+            kind,
+            severity,
+            message: message(),
+            suppressed,
+        };
+        match &self.sink {
+            Sink::Stderr => eprintln!("{}", diagnostic),
+            Sink::Callback(callback) => callback(&diagnostic),
+            Sink::Silent => {}
+        }
+    }
+
+    /// Reports through the default sink when no sink is configured.
+    pub(crate) fn report_to(
+        diagnostics: Option<&Diagnostics>,
+        kind: DiagnosticKind,
+        message: impl FnOnce() -> String,
+    ) {
+        // Shared by every logger without a sink, as they all write to the same stderr
+        static DEFAULT: OnceLock<Diagnostics> = OnceLock::new();
+        diagnostics
+            .unwrap_or_else(|| DEFAULT.get_or_init(Diagnostics::default))
+            .report(kind, message);
+    }
+
+    /// Applies the rate limit, returning the suppressed count to attach, or
+    /// `None` if this diagnostic should be suppressed.
+    fn admit(&self, kind: DiagnosticKind) -> Option<u64> {
+        let Some((max, period)) = self.rate_limit else {
+            return Some(0);
+        };
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(kind).or_insert(Window {
+            started: now,
+            reported: 0,
+            suppressed: 0,
+        });
+
+        if now.duration_since(window.started) >= period {
+            window.started = now;
+            window.reported = 0;
+        }
+
+        if window.reported < max {
+            window.reported += 1;
+            Some(std::mem::take(&mut window.suppressed))
+        } else {
+            window.suppressed += 1;
+            None
+        }
+    }
+}
+
+impl Default for Diagnostics {
+    /// Writes to stderr, rate limited per kind.
+    fn default() -> Self {
+        Self::stderr().with_rate_limit(DEFAULT_MAX_PER_WINDOW, DEFAULT_WINDOW)
+    }
+}
+
+impl fmt::Debug for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sink = match self.sink {
+            Sink::Stderr => "stderr",
+            Sink::Callback(_) => "<callback fn>",
+            Sink::Silent => "silent",
+        };
+        f.debug_struct("Diagnostics")
+            .field("sink", &sink)
+            .field("min_severity", &self.min_severity)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
+}
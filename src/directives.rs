@@ -0,0 +1,156 @@
+use crate::logger_levels::LoggerLevels;
+use logform::LogInfo;
+
+/// The level that turns off every entry from a target.
+pub(crate) const OFF: &str = "off";
+
+/// `RUST_LOG`-style level directives, e.g. `info,my_crate::db=debug,hyper=warn`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct Directives {
+    /// The level from a directive without a target
+    pub(crate) level: Option<String>,
+    pub(crate) targets: Vec<(String, String)>,
+    /// Directives that could not be parsed
+    pub(crate) invalid: Vec<String>,
+}
+
+impl Directives {
+    /// Parses a comma-separated list of `level`, `target` and `target=level`
+    /// directives. A bare name is a level if it is `off` or one of `levels`,
+    /// and otherwise a target enabled at the most verbose of `levels`. A
+    /// `target=level` directive whose level is neither is invalid. Later
+    /// directives win over earlier ones for the same target.
+    pub(crate) fn parse(spec: &str, levels: &LoggerLevels) -> Self {
+        let mut directives = Directives::default();
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let mut parts = directive.split('=').map(str::trim);
+            let is_level = |level: &str| level == OFF || levels.get_severity(level).is_some();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(name), None, None) => {
+                    let level = name.to_lowercase();
+                    if is_level(&level) {
+                        directives.level = Some(level);
+                    } else if let Some(most_verbose) = levels.most_verbose() {
+                        directives
+                            .targets
+                            .push((name.to_string(), most_verbose.to_string()));
+                    } else {
+                        directives.invalid.push(directive.to_string());
+                    }
+                }
+                (Some(target), Some(level), None)
+                    if !target.is_empty() && is_level(&level.to_lowercase()) =>
+                {
+                    directives
+                        .targets
+                        .push((target.to_string(), level.to_lowercase()));
+                }
+                _ => directives.invalid.push(directive.to_string()),
+            }
+        }
+        directives
+    }
+}
+
+/// A target's level, with the severity threshold it results in.
+#[derive(Debug)]
+pub(crate) struct TargetLevel {
+    pub(crate) target: String,
+    pub(crate) level: String,
+    pub(crate) threshold: Threshold,
+}
+
+/// How verbose entries from a target may be before no transport accepts them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Threshold {
+    Off,
+    /// The highest severity number accepted, or `None` if every level is accepted
+    Max(Option<u8>),
+}
+
+/// Returns the `target` and `module_path` recorded in an entry's meta.
+pub(crate) fn entry_target(entry: &LogInfo) -> (Option<&str>, Option<&str>) {
+    let get = |key| entry.meta.get(key).and_then(|value| value.as_str());
+    (get("target"), get("module_path"))
+}
+
+/// Finds the most specific target level matching `target` or, failing that, `module_path`.
+///
+/// `target_levels` must be sorted with the longest targets first.
+pub(crate) fn find<'a>(
+    target_levels: &'a [TargetLevel],
+    target: Option<&str>,
+    module_path: Option<&str>,
+) -> Option<&'a TargetLevel> {
+    let lookup = |name: &str| {
+        target_levels
+            .iter()
+            .find(|target_level| matches(&target_level.target, name))
+    };
+    target
+        .and_then(lookup)
+        .or_else(|| module_path.and_then(lookup))
+}
+
+/// Returns `true` if `name` is `target` or one of its submodules.
+fn matches(target: &str, name: &str) -> bool {
+    name.strip_prefix(target)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let directives = Directives::parse(
+            "INFO, my_crate::db=debug,hyper = warn,,=trace,a=b=c,sql=bogus,db=",
+            &LoggerLevels::default(),
+        );
+        assert_eq!(directives.level.as_deref(), Some("info"));
+        assert_eq!(
+            directives.targets,
+            [
+                ("my_crate::db".to_string(), "debug".to_string()),
+                ("hyper".to_string(), "warn".to_string()),
+            ]
+        );
+        assert_eq!(directives.invalid, ["=trace", "a=b=c", "sql=bogus", "db="]);
+    }
+
+    #[test]
+    fn test_parse_bare_target() {
+        let levels = LoggerLevels::new([("error", 0), ("notice", 1), ("trace", 2)]);
+        let directives = Directives::parse("warn,My_App,notice", &levels);
+        assert_eq!(directives.level.as_deref(), Some("notice"));
+        assert_eq!(
+            directives.targets,
+            [
+                ("warn".to_string(), "trace".to_string()),
+                ("My_App".to_string(), "trace".to_string()),
+            ]
+        );
+        assert!(directives.invalid.is_empty());
+    }
+
+    #[test]
+    fn test_find_most_specific_target() {
+        let level = |target: &str, level: &str| TargetLevel {
+            target: target.to_string(),
+            level: level.to_string(),
+            threshold: Threshold::Max(None),
+        };
+        let target_levels = [level("my_crate::db", "debug"), level("my_crate", "warn")];
+        let found = |target, module_path| {
+            find(&target_levels, target, module_path).map(|t| t.level.as_str())
+        };
+
+        assert_eq!(found(Some("my_crate::db::pool"), None), Some("debug"));
+        assert_eq!(found(Some("my_crate::http"), None), Some("warn"));
+        assert_eq!(found(Some("my_crate"), None), Some("warn"));
+        assert_eq!(found(Some("my_crate_extra"), None), None);
+        assert_eq!(found(Some("sql"), Some("my_crate::db")), Some("debug"));
+        assert_eq!(found(None, None), None);
+    }
+}
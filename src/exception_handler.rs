@@ -0,0 +1,172 @@
+use crate::logger::{Logger, SharedState};
+use logform::LogInfo;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    backtrace::Backtrace,
+    panic::{self, PanicHookInfo},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+// How long the hook waits for the configuration lock before giving up. The
+// panicking thread may itself hold the write lock (e.g. inside `configure`).
+const STATE_LOCK_TIMEOUT: Duration = Duration::from_millis(100);
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// A panic hook that writes uncaught panics to a logger's exception handlers.
+///
+/// The hook chains to whichever hook was installed before it. Uninstalling
+/// puts that hook back if this one is still the current hook. If the
+/// application has installed another hook since, which chains to this one in
+/// turn, this hook stays in the chain and is only deactivated.
+pub(crate) struct ExceptionHandler {
+    active: Arc<AtomicBool>,
+    previous: Arc<Mutex<Option<PanicHook>>>,
+    // The address of the installed hook, to tell whether it is still current
+    hook: usize,
+}
+
+impl ExceptionHandler {
+    pub(crate) fn install(shared_state: Weak<RwLock<SharedState>>) -> Self {
+        let previous = Arc::new(Mutex::new(Some(panic::take_hook())));
+        let active = Arc::new(AtomicBool::new(true));
+
+        let hook_active = Arc::clone(&active);
+        let hook_previous = Arc::clone(&previous);
+        let hook: PanicHook = Box::new(move |info| {
+            if hook_active.load(Ordering::Acquire) {
+                // The logger may have been dropped since
+                if let Some(shared_state) = shared_state.upgrade() {
+                    Self::log_panic(info, &shared_state);
+                }
+            }
+            if let Some(previous) = &*hook_previous.lock() {
+                previous(info);
+            }
+        });
+        let address = hook_address(&hook);
+        panic::set_hook(hook);
+
+        ExceptionHandler {
+            active,
+            previous,
+            hook: address,
+        }
+    }
+
+    /// Stops logging panics, putting the previous hook back if no other hook
+    /// has been installed since.
+    pub(crate) fn uninstall(self) {
+        self.active.store(false, Ordering::Release);
+        // The hook can't be changed while unwinding, so it just stays deactivated
+        if thread::panicking() {
+            return;
+        }
+
+        let current = panic::take_hook();
+        if hook_address(&current) == self.hook {
+            if let Some(previous) = self.previous.lock().take() {
+                drop(current);
+                panic::set_hook(previous);
+                return;
+            }
+        }
+        panic::set_hook(current);
+    }
+
+    fn log_panic(info: &PanicHookInfo<'_>, shared_state: &RwLock<SharedState>) {
+        let Some(state) = shared_state.try_read_for(STATE_LOCK_TIMEOUT) else {
+            // The diagnostics sink lives behind the same lock, so fall back to stderr
+            eprintln!("[winston] Unable to log panic: logger configuration is locked.");
+            return;
+        };
+
+        let mut entry = Self::panic_entry(info);
+        // Exception handlers bypass the worker, so merge the logger-wide metadata here
+        for (key, value) in state.options.default_meta.iter().flatten() {
+            entry
+                .meta
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        let handlers = state
+            .options
+            .exception_handlers
+            .as_ref()
+            .filter(|handlers| !handlers.is_empty());
+
+        match handlers {
+            Some(handlers) => {
+                for handler in handlers {
+                    let format = handler
+                        .get_format()
+                        .or_else(|| state.options.format.clone());
+                    let formatted = match format {
+                        Some(format) => format.transform(entry.clone()),
+                        None => Some(entry.clone()),
+                    };
+                    if let Some(msg) = formatted {
+                        handler.get_transport().log(msg);
+                    }
+                    let _ = handler.get_transport().flush();
+                }
+            }
+            None => {
+                // Fall back to the logger's own transports
+                Logger::process_entry(&Arc::new(entry), &state);
+                if let Some(transports) = &state.options.transports {
+                    for (_handle, transport) in transports {
+                        let _ = transport.get_transport().flush();
+                    }
+                }
+            }
+        }
+    }
+
+    fn panic_entry(info: &PanicHookInfo<'_>) -> LogInfo {
+        let payload = info.payload();
+        let message = if let Some(s) = payload.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = payload.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Box<dyn Any>".to_string()
+        };
+
+        let mut entry = LogInfo::new("error", format!("uncaught panic: {}", message))
+            .with_meta("panic", true)
+            .with_meta("thread", thread::current().name().unwrap_or("<unnamed>"))
+            .with_meta("backtrace", Backtrace::force_capture().to_string());
+
+        if let Some(location) = info.location() {
+            entry = entry.with_meta(
+                "location",
+                format!(
+                    "{}:{}:{}",
+                    location.file(),
+                    location.line(),
+                    location.column()
+                ),
+            );
+        }
+
+        entry
+    }
+}
+
+fn hook_address(hook: &PanicHook) -> usize {
+    &**hook as *const _ as *const () as usize
+}
+
+impl std::fmt::Debug for ExceptionHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExceptionHandler")
+            .field("active", &self.active.load(Ordering::Relaxed))
+            .finish()
+    }
+}
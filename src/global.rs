@@ -1,4 +1,4 @@
-use crate::{logger::TransportHandle, Logger};
+use crate::{logger::TransportHandle, Logger, Profiler};
 use logform::LogInfo;
 use std::sync::OnceLock;
 use winston_transport::Transport;
@@ -49,6 +49,14 @@ pub fn is_initialized() -> bool {
     GLOBAL_LOGGER.get().is_some()
 }
 
+/// Returns `true` if the global logger would accept an entry at `level`.
+///
+/// # Panics
+/// Panics if `init()` hasn't been called yet.
+pub fn is_enabled(level: &str) -> bool {
+    global_logger().is_enabled(level)
+}
+
 pub fn log(entry: logform::LogInfo) {
     global_logger().log(entry);
 }
@@ -64,6 +72,30 @@ pub fn try_log(entry: logform::LogInfo) -> bool {
     }
 }
 
+/// Starts a timer on the global logger. See [`Logger::start_timer`].
+///
+/// # Panics
+/// Panics if `init()` hasn't been called yet.
+pub fn start_timer() -> Profiler {
+    global_logger().start_timer()
+}
+
+/// Starts or stops a profile on the global logger. See [`Logger::profile`].
+///
+/// # Panics
+/// Panics if `init()` hasn't been called yet.
+pub fn profile(id: impl Into<String>) {
+    global_logger().profile(id);
+}
+
+/// Like [`profile`], but logs `entry` when the profile stops.
+///
+/// # Panics
+/// Panics if `init()` hasn't been called yet.
+pub fn profile_with(id: impl Into<String>, entry: LogInfo) {
+    global_logger().profile_with(id, entry);
+}
+
 pub fn configure(new_options: Option<crate::LoggerOptions>) {
     global_logger().configure(new_options);
 }
@@ -1,3 +1,11 @@
+#[cfg(feature = "async")]
+mod async_support;
+mod child_logger;
+#[cfg(feature = "config")]
+mod config;
+mod diagnostics;
+mod directives;
+mod exception_handler;
 mod global;
 mod log_macros;
 mod logger;
@@ -5,16 +13,31 @@ mod logger_builder;
 mod logger_levels;
 mod logger_options;
 mod logger_transport;
+mod profiler;
+mod rate_limit;
+mod sampling;
+mod spool;
+mod stats;
 pub mod transports;
 
+pub use child_logger::ChildLogger;
+#[cfg(feature = "config")]
+pub use config::{ConfigFormat, ConfigLoader, TransportConfig, TransportFactory};
+pub use diagnostics::{Diagnostic, DiagnosticKind, DiagnosticSeverity, Diagnostics};
 #[cfg(feature = "log-backend")]
 pub use global::register_with_log;
 pub use global::{
-    add_transport, close, configure, flush, init, is_initialized, log, query, remove_transport,
-    try_log,
+    add_transport, close, configure, flush, init, is_enabled, is_initialized, log, profile,
+    profile_with, query, remove_transport, start_timer, try_log,
 };
 pub use logform as format;
-pub use logger::Logger;
-pub use logger_options::{BackpressureStrategy, LoggerOptions};
-pub use logger_transport::LoggerTransport;
+pub use logger::{Logger, TransportHandle};
+pub use logger_builder::LoggerBuilder;
+pub use logger_levels::LevelFilter;
+pub use logger_options::{BackpressureStrategy, BufferOverflowPolicy, ErrorHandler, LoggerOptions};
+pub use logger_transport::{LogFilter, LoggerTransport};
+pub use profiler::Profiler;
+pub use rate_limit::RateLimiter;
+pub use sampling::Sampler;
+pub use stats::LoggerStats;
 pub use winston_transport::LogQuery;
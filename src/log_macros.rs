@@ -1,37 +1,150 @@
+// The logging macros check the level before evaluating the message or metadata
+// expressions, so disabled levels cost a single lookup.
 #[macro_export]
 macro_rules! log {
+    // Builds an entry from a message and a collection of metadata
+    (@meta (logger $logger:expr) $level:ident, $message:expr, $meta:expr) => {{
+        let logger = &$logger;
+        if logger.is_enabled(stringify!($level)) {
+            let entry = $crate::format::LogInfo::new(stringify!($level), $message);
+
+            // Iterate over the metadata and add it to the log entry
+            let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+
+            logger.log(entry);
+        }
+    }};
+
+    (@meta (global) $level:ident, $message:expr, $meta:expr) => {{
+        if $crate::is_enabled(stringify!($level)) {
+            let entry = $crate::format::LogInfo::new(stringify!($level), $message);
+
+            // Iterate over the metadata and add it to the log entry
+            let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+
+            $crate::log(entry);
+        }
+    }};
+
+    // A `key = value` pair would also parse as a metadata expression below
+    ($level:ident, $fmt:literal, $key:ident = $($rest:tt)*) => {
+        $crate::__log_format!(@munch (global) $level, $fmt; [] [] [] , $key = $($rest)*)
+    };
+
+    // A literal message with metadata, e.g. log!(info, "Started", meta!(port = 8080)).
+    // A single argument after the message is always metadata, as before format
+    // strings were supported; use `{name}` captures or `key = value` instead.
+    ($level:ident, $message:literal, $meta:expr $(,)?) => {
+        $crate::log!(@meta (global) $level, $message, $meta)
+    };
+
+    // Format string with the global logger, e.g. log!(info, "user {} logged in from {ip}", id, key = value)
+    ($level:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__log_format!(@munch (global) $level, $fmt; [] [] [] $($rest)*)
+    };
+
+    // The same with a logger
+    ($logger:expr, $level:ident, $fmt:literal, $key:ident = $($rest:tt)*) => {
+        $crate::__log_format!(@munch (logger $logger) $level, $fmt; [] [] [] , $key = $($rest)*)
+    };
+
+    ($logger:expr, $level:ident, $message:literal, $meta:expr $(,)?) => {
+        $crate::log!(@meta (logger $logger) $level, $message, $meta)
+    };
+
+    // Format string with a logger, e.g. log!(logger, info, "user {} logged in", id, key = value)
+    ($logger:expr, $level:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__log_format!(@munch (logger $logger) $level, $fmt; [] [] [] $($rest)*)
+    };
+
     // First case: No logger, simple logging
     ($level:ident, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {{
-        let entry = $crate::format::LogInfo::new(stringify!($level), $message)
-            $(.with_meta(stringify!($key), $value))*;
-        $crate::log(entry);
+        if $crate::is_enabled(stringify!($level)) {
+            let entry = $crate::format::LogInfo::new(stringify!($level), $message)
+                $(.with_meta(stringify!($key), $value))*;
+            $crate::log(entry);
+        }
     }};
 
     // Second case: With logger and key-value metadata
     ($logger:expr, $level:ident, $message:expr $(, $key:ident = $value:expr)* $(,)?) => {{
-        let entry = $crate::format::LogInfo::new(stringify!($level), $message)
-            $(.with_meta(stringify!($key), $value))*;
-        $logger.log(entry);
+        let logger = &$logger;
+        if logger.is_enabled(stringify!($level)) {
+            let entry = $crate::format::LogInfo::new(stringify!($level), $message)
+                $(.with_meta(stringify!($key), $value))*;
+            logger.log(entry);
+        }
     }};
 
     // Third case: With logger and metadata as an expression (e.g., meta!(key1 = value1, key2 = value2))
-    ($logger:expr, $level:ident, $message:expr, $meta:expr) => {{
-        let entry = $crate::format::LogInfo::new(stringify!($level), $message);
+    ($logger:expr, $level:ident, $message:expr, $meta:expr) => {
+        $crate::log!(@meta (logger $logger) $level, $message, $meta)
+    };
 
-        // Iterate over the metadata and add it to the log entry
-        let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+    // Fourth case: No logger and with metadata as an expression (e.g., meta!(key1 = value1, key2 = value2))
+    ($level:ident, $message:expr, $meta:expr) => {
+        $crate::log!(@meta (global) $level, $message, $meta)
+    };
+}
 
-        $logger.log(entry);
-    }};
+// Splits the arguments that follow a format string into positional format
+// arguments, `key = value` metadata and `meta!(...)` collections, then builds
+// the entry once the level check passes. Each `key = value` pair is bound to a
+// local named `key` before formatting, so the format string can also refer to
+// it by name, as in `"{ip} connected", ip = addr`; it is recorded as metadata
+// either way.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_format {
+    // meta!(...) collection
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] , meta!($($m:tt)*) $($rest:tt)*) => {
+        $crate::__log_format!(@munch $target $level, $fmt; [$($args)*] [$($kv)*] [$($metas)* ($crate::meta!($($m)*))] $($rest)*)
+    };
 
-    // Fourth case: No logger and with metadata as an expression (e.g., meta!(key1 = value1, key2 = value2))
-    ($level:ident, $message:expr, $meta:expr) => {{
-        let entry = $crate::format::LogInfo::new(stringify!($level), $message);
+    // key = value metadata
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] , $key:ident = $value:expr , $($rest:tt)*) => {
+        $crate::__log_format!(@munch $target $level, $fmt; [$($args)*] [$($kv)* ($key, $value)] [$($metas)*] , $($rest)*)
+    };
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] , $key:ident = $value:expr) => {
+        $crate::__log_format!(@munch $target $level, $fmt; [$($args)*] [$($kv)* ($key, $value)] [$($metas)*])
+    };
 
-        // Iterate over the metadata and add it to the log entry
-        let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+    // Positional format argument
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] , $arg:expr , $($rest:tt)*) => {
+        $crate::__log_format!(@munch $target $level, $fmt; [$($args)* ($arg)] [$($kv)*] [$($metas)*] , $($rest)*)
+    };
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] , $arg:expr) => {
+        $crate::__log_format!(@munch $target $level, $fmt; [$($args)* ($arg)] [$($kv)*] [$($metas)*])
+    };
 
-        $crate::log(entry);
+    // End of input, with or without a trailing comma
+    (@munch $target:tt $level:ident, $fmt:literal; [$($args:tt)*] [$($kv:tt)*] [$($metas:tt)*] $(,)?) => {
+        $crate::__log_format!(@emit $target $level, $fmt; [$($args)*] [$($kv)*] [$($metas)*])
+    };
+
+    (@emit (global) $level:ident, $fmt:literal; [$(($arg:expr))*] [$(($key:ident, $value:expr))*] [$(($meta:expr))*]) => {{
+        if $crate::is_enabled(stringify!($level)) {
+            $(let $key = $value;)*
+            let entry = $crate::format::LogInfo::new(stringify!($level), format!($fmt $(, $arg)*))
+                $(.with_meta(stringify!($key), $key))*;
+            $(
+                let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+            )*
+            $crate::log(entry);
+        }
+    }};
+
+    (@emit (logger $logger:expr) $level:ident, $fmt:literal; [$(($arg:expr))*] [$(($key:ident, $value:expr))*] [$(($meta:expr))*]) => {{
+        let logger = &$logger;
+        if logger.is_enabled(stringify!($level)) {
+            $(let $key = $value;)*
+            let entry = $crate::format::LogInfo::new(stringify!($level), format!($fmt $(, $arg)*))
+                $(.with_meta(stringify!($key), $key))*;
+            $(
+                let entry = $meta.into_iter().fold(entry, |acc, (key, value)| acc.with_meta(key, value));
+            )*
+            logger.log(entry);
+        }
     }};
 }
 
@@ -48,16 +161,14 @@ macro_rules! meta {
 
 #[macro_export]
 macro_rules! create_log_methods {
-    ($($level:ident),*) => {
-        pub trait LoggerMethods {
-            $(
-                fn $level(&self, message: &str, metadata: Option<Vec<(&'static str, serde_json::Value)>>);
-            )*
-        }
-
-        impl LoggerMethods for $crate::Logger {
+    // Implements the trait for one logger type
+    (@impl $logger:ty; $($level:ident),*) => {
+        impl LoggerMethods for $logger {
             $(
                 fn $level(&self, message: &str, metadata: Option<Vec<(&'static str, serde_json::Value)>>) {
+                    if !self.is_enabled(stringify!($level)) {
+                        return;
+                    }
                     let mut entry = $crate::format::LogInfo::new(stringify!($level), message);
                     if let Some(meta) = metadata {
                         for (key, value) in meta {
@@ -69,50 +180,48 @@ macro_rules! create_log_methods {
             )*
         }
     };
+
+    ($($level:ident),*) => {
+        pub trait LoggerMethods {
+            $(
+                fn $level(&self, message: &str, metadata: Option<Vec<(&'static str, serde_json::Value)>>);
+            )*
+        }
+
+        $crate::create_log_methods!(@impl $crate::Logger; $($level),*);
+        $crate::create_log_methods!(@impl $crate::ChildLogger; $($level),*);
+    };
 }
 
 #[macro_export]
 macro_rules! create_level_macros {
     ($($level:ident),*) => {
+        $crate::create_level_macros!(@with_dollar ($) $($level),*);
+    };
+
+    // The generated macros need their own repetitions, so `$` is passed in as a token
+    (@with_dollar ($d:tt) $($level:ident),*) => {
         $(
             macro_rules! $level {
-                // using the @global is unclean, this would still allow them pass in string literals naturally whilst keeping the @global arm for flexibility of passing the message via an expression
-                ($message:literal, $meta:expr) => {{
-                    let mut entry = $crate::format::LogInfo::new(stringify!($level), $message);
-                    for (key, value) in $meta {
-                        entry = entry.with_meta(key, value);
-                    }
-                    $crate::log(entry);
-                }};
-
-                // First arm: Log without metadata
-                ($logger:expr, $message:expr) => {
-                    $crate::log!($logger, $level, $message);
+                // Log with metadata using the global logger and a message expression
+                (@global, $d message:expr, $d meta:expr) => {
+                    $crate::log!($level, $d message, $d meta)
                 };
 
-                // Second arm: Log with metadata
-                ($logger:expr, $message:expr, $meta:expr) => {{
-                    let mut entry = $crate::format::LogInfo::new(stringify!($level), $message);
-                    for (key, value) in $meta {
-                        entry = entry.with_meta(key, value);
-                    }
-                    $logger.log(entry);
-                }};
+                // Format string with the global logger, e.g. info!("user {} logged in", id, key = value)
+                ($d fmt:literal $d($d rest:tt)*) => {
+                    $crate::log!($level, $d fmt $d($d rest)*)
+                };
 
-                // Third arm: Log without metadata using the global logger
-                ($message:expr) => {
-                    $crate::log!($level, $message);
+                // Message expression with the global logger
+                ($d message:expr) => {
+                    $crate::log!($level, $d message)
                 };
 
-                // Fourth arm: Log with metadata using the global logger
-                // Modified to use a special marker to distinguish from the first arm
-               (@global, $message:expr, $meta:expr) => {{
-                    let mut entry = $crate::format::LogInfo::new(stringify!($level), $message);
-                    for (key, value) in $meta {
-                        entry = entry.with_meta(key, value);
-                    }
-                    $crate::log(entry);
-                }};
+                // Everything else goes to the given logger, e.g. info!(logger, "user {} of {}", id, total)
+                ($d logger:expr, $d($d rest:tt)+) => {
+                    $crate::log!($d logger, $level, $d($d rest)+)
+                };
             }
         )*
     };
@@ -1,24 +1,49 @@
+#[cfg(feature = "async")]
+use crate::async_support::{CapacityWaiters, Signal};
+#[cfg(feature = "config")]
+use crate::config::{self, ConfigChange, ConfigLoader, ConfigWatch};
 use crate::{
+    child_logger::ChildLogger,
+    diagnostics::{DiagnosticKind, Diagnostics},
+    directives::{self, TargetLevel, Threshold},
+    exception_handler::ExceptionHandler,
     logger_builder::LoggerBuilder,
-    logger_options::{BackpressureStrategy, LoggerOptions},
+    logger_levels::LevelFilter,
+    logger_options::{BackpressureStrategy, BufferOverflowPolicy, LoggerOptions},
     logger_transport::{IntoLoggerTransport, LoggerTransport},
+    profiler::{self, Profiler},
+    rate_limit::{RateLimiter, Verdict},
+    sampling::{Sample, Sampler},
+    spool::Spool,
+    stats::{LoggerStats, StatsCounters},
+};
+use crossbeam_channel::{
+    bounded, select, Receiver, RecvTimeoutError, SendError, SendTimeoutError, Sender, TrySendError,
 };
-use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
 use logform::LogInfo;
 use parking_lot::RwLock;
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
+    fmt,
     sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
-        Arc, Condvar, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
     thread,
+    time::{Duration, Instant},
 };
 use winston_transport::{LogQuery, Transport};
 
 // Static counter for generating unique transport IDs
 static NEXT_TRANSPORT_ID: AtomicUsize = AtomicUsize::new(0);
 
+// How often a watched config file is checked for changes by default
+#[cfg(feature = "config")]
+const CONFIG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// How often an idle worker checks the spill file for entries to replay
+const SPOOL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// A handle for referencing and removing transports
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct TransportHandle(usize);
@@ -29,6 +54,12 @@ impl TransportHandle {
     }
 }
 
+impl fmt::Display for TransportHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "transport #{}", self.0)
+    }
+}
+
 /// Builder for configuring a transport before adding it to the logger
 pub struct TransportBuilder<'a> {
     logger: &'a Logger,
@@ -42,6 +73,21 @@ impl<'a> TransportBuilder<'a> {
         self
     }
 
+    /// Restrict this transport to the levels selected by a filter
+    pub fn with_level_filter(mut self, filter: LevelFilter) -> Self {
+        self.logger_transport = self.logger_transport.with_level_filter(filter);
+        self
+    }
+
+    /// Add a predicate that entries must satisfy to reach this transport
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&LogInfo) -> bool + Send + Sync + 'static,
+    {
+        self.logger_transport = self.logger_transport.with_filter(filter);
+        self
+    }
+
     /// Set a custom format for this transport
     pub fn with_format<F>(mut self, format: F) -> Self
     where
@@ -55,12 +101,13 @@ impl<'a> TransportBuilder<'a> {
     pub fn add(self) -> TransportHandle {
         let handle = TransportHandle::new();
 
-        let mut state = self.logger.shared_state.write();
+        let mut state = self.logger.inner.shared_state.write();
         if let Some(transports) = &mut state.options.transports {
             transports.push((handle, self.logger_transport));
         } else {
             state.options.transports = Some(vec![(handle, self.logger_transport)]);
         }
+        Logger::refresh_effective_levels(&mut state);
 
         handle
     }
@@ -69,27 +116,93 @@ impl<'a> TransportBuilder<'a> {
 #[derive(Debug)]
 pub enum LogMessage {
     Entry(Arc<LogInfo>),
-    Configure(LoggerOptions),
+    /// Options applied by the worker between entries, keeping the current
+    /// value of any option that isn't set. Nothing in the crate sends it.
+    #[allow(dead_code)]
+    Configure(Box<LoggerOptions>),
     Shutdown,
-    Flush,
+    /// Flush requested by [`Logger::flush`], acknowledged once the transports are flushed
+    Flush(Sender<Result<(), String>>),
+    /// Flush requested by [`Logger::flush_async`], acknowledged through the signal
+    #[cfg(feature = "async")]
+    FlushAsync(Arc<Signal<Result<(), String>>>),
+    /// Sent by [`Logger::set_channel_capacity`] as the last message on the old
+    /// channel; the worker continues with these receivers.
+    Resize {
+        receiver: Arc<Receiver<LogMessage>>,
+        priority: Receiver<Arc<LogInfo>>,
+    },
+    /// Sent by [`Logger::watch_config`] and [`Logger::unwatch_config`] to
+    /// start or stop polling a config file.
+    #[cfg(feature = "config")]
+    Watch(Option<Box<ConfigWatch>>),
+}
+
+/// The logger's ends of the worker channel and priority lane.
+///
+/// Replaced as a whole when the channel capacity changes.
+#[derive(Debug)]
+struct Channel {
+    sender: Sender<LogMessage>,
+    receiver: Arc<Receiver<LogMessage>>,
+    // Lane for entries at or above `priority_level`, drained before the channel
+    priority_sender: Sender<Arc<LogInfo>>,
 }
 
 #[derive(Debug)]
 pub(crate) struct SharedState {
     pub(crate) options: LoggerOptions,
     // Cache the minimum severity needed for any transport to accept a log
-    min_required_severity: Option<u8>,
+    min_required: Threshold,
+    // The same for entries matching no target level
+    default_threshold: Threshold,
+    // The target levels resolved against the level table, most specific first
+    target_levels: Vec<TargetLevel>,
+    pub(crate) stats: Arc<StatsCounters>,
+    // Overflow file used by `BackpressureStrategy::SpillToFile`
+    spool: Option<Arc<Spool>>,
+    // The hook logging uncaught panics, while installed
+    panic_hook: Option<ExceptionHandler>,
+}
+
+impl SharedState {
+    /// Reports through the configured diagnostics sink, or stderr if there is none.
+    pub(crate) fn report(&self, kind: DiagnosticKind, message: impl FnOnce() -> String) {
+        Diagnostics::report_to(self.options.diagnostics.as_ref(), kind, message);
+    }
+}
+
+/// Where the worker sends formatted entries: straight to each transport, or
+/// collected per transport while it handles a batch.
+enum Output {
+    Direct,
+    Batched(HashMap<TransportHandle, Vec<LogInfo>>),
 }
 
 #[derive(Debug)]
 pub struct Logger {
+    pub(crate) inner: Arc<LoggerInner>,
+    // Only the handle returned by `Logger::new` closes the logger when dropped;
+    // the handles held by child loggers don't
+    owner: bool,
+}
+
+/// The state behind a [`Logger`], shared with its child loggers.
+#[derive(Debug)]
+pub(crate) struct LoggerInner {
     worker_thread: Mutex<Option<thread::JoinHandle<()>>>,
-    sender: Sender<LogMessage>,
-    receiver: Arc<Receiver<LogMessage>>,
+    channel: RwLock<Channel>,
     pub(crate) shared_state: Arc<RwLock<SharedState>>,
     buffer: Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
-    flush_complete: Arc<(Mutex<bool>, Condvar)>,
     is_closed: AtomicBool,
+    profiles: Mutex<HashMap<String, Instant>>,
+    stats: Arc<StatsCounters>,
+    // Counts overflowing entries under `BackpressureStrategy::Sample`
+    sample_counter: AtomicU64,
+    #[cfg(feature = "async")]
+    capacity_waiters: Arc<CapacityWaiters>,
+    #[cfg(feature = "async")]
+    worker_finished: Arc<Signal>,
 }
 
 impl Logger {
@@ -97,54 +210,118 @@ impl Logger {
         let options = options.unwrap_or_default();
         let capacity = options.channel_capacity.unwrap_or(1024);
         let (sender, receiver) = bounded(capacity);
-        let flush_complete = Arc::new((Mutex::new(false), Condvar::new()));
+        let (priority_sender, priority_receiver) = bounded(capacity);
 
         let shared_receiver = Arc::new(receiver);
         // Pre-compute effective levels
-        let min_required_severity = Self::compute_min_severity(&options);
-        let shared_state = Arc::new(RwLock::new(SharedState {
+        let stats = Arc::new(StatsCounters::default());
+        let mut state = SharedState {
             options,
-            min_required_severity,
-        }));
+            min_required: Threshold::Max(None),
+            default_threshold: Threshold::Max(None),
+            target_levels: Vec::new(),
+            stats: Arc::clone(&stats),
+            spool: None,
+            panic_hook: None,
+        };
+        Self::refresh_effective_levels(&mut state);
+        if let Some(strategy) = state.options.backpressure_strategy.clone() {
+            Self::apply_backpressure_strategy(&mut state, strategy);
+        }
+        let shared_state = Arc::new(RwLock::new(state));
 
         let buffer = Arc::new(Mutex::new(VecDeque::new()));
 
         let worker_receiver = Arc::clone(&shared_receiver);
         let worker_shared_state = Arc::clone(&shared_state);
         let worker_buffer = Arc::clone(&buffer);
-        let worker_flush_complete = Arc::clone(&flush_complete);
+        #[cfg(feature = "async")]
+        let capacity_waiters = Arc::new(CapacityWaiters::default());
+        #[cfg(feature = "async")]
+        let worker_capacity_waiters = Arc::clone(&capacity_waiters);
+        #[cfg(feature = "async")]
+        let worker_finished = Signal::new();
+        #[cfg(feature = "async")]
+        let worker_finished_signal = Arc::clone(&worker_finished);
 
         // Spawn a worker thread to handle logging
         let worker_thread = thread::spawn(move || {
             Self::worker_loop(
                 worker_receiver,
+                priority_receiver,
                 worker_shared_state,
                 worker_buffer,
-                worker_flush_complete,
+                #[cfg(feature = "async")]
+                worker_capacity_waiters,
             );
+            #[cfg(feature = "async")]
+            worker_finished_signal.complete(());
         });
 
-        Logger {
+        let inner = LoggerInner {
             worker_thread: Mutex::new(Some(worker_thread)),
-            sender,
+            channel: RwLock::new(Channel {
+                sender,
+                receiver: shared_receiver,
+                priority_sender,
+            }),
             shared_state,
             buffer,
-            receiver: shared_receiver,
-            flush_complete,
             is_closed: AtomicBool::new(false),
+            profiles: Mutex::new(HashMap::new()),
+            stats,
+            sample_counter: AtomicU64::new(0),
+            #[cfg(feature = "async")]
+            capacity_waiters,
+            #[cfg(feature = "async")]
+            worker_finished,
+        };
+        let logger = Logger {
+            inner: Arc::new(inner),
+            owner: true,
+        };
+
+        let shared_state = &logger.inner.shared_state;
+        Self::install_configured_panic_hook(&mut shared_state.write(), shared_state);
+
+        logger
+    }
+
+    /// Installs the panic hook if exception handlers are configured and it
+    /// isn't installed yet.
+    fn install_configured_panic_hook(
+        state: &mut SharedState,
+        shared_state: &Arc<RwLock<SharedState>>,
+    ) {
+        let has_exception_handlers = state
+            .options
+            .exception_handlers
+            .as_ref()
+            .is_some_and(|handlers| !handlers.is_empty());
+        if has_exception_handlers && state.panic_hook.is_none() {
+            state.panic_hook = Some(ExceptionHandler::install(Arc::downgrade(shared_state)));
         }
     }
 
     fn compute_min_severity(options: &LoggerOptions) -> Option<u8> {
+        Self::compute_min_severity_at(options, options.level.as_deref())
+    }
+
+    /// Like [`compute_min_severity`](Self::compute_min_severity), with `level`
+    /// in place of the logger's level.
+    fn compute_min_severity_at(options: &LoggerOptions, level: Option<&str>) -> Option<u8> {
         let levels = options.levels.as_ref()?;
-        let mut min_severity = options
-            .level
-            .as_deref()
-            .and_then(|lvl| levels.get_severity(lvl));
+        let mut min_severity = level.and_then(|lvl| levels.get_severity(lvl));
 
         if let Some(transports) = &options.transports {
             for (_handle, transport) in transports {
-                if let Some(transport_level) = transport.get_level() {
+                if let Some(filter) = transport.get_level_filter() {
+                    if let Some(filter_severity) = filter.max_severity(levels) {
+                        min_severity = Some(
+                            min_severity.map_or(filter_severity, |cur| cur.max(filter_severity)),
+                        );
+                    }
+                } else if let Some(transport_level) = transport.get_level() {
                     if let Some(transport_severity) = levels.get_severity(transport_level) {
                         min_severity = Some(
                             min_severity
@@ -160,99 +337,599 @@ impl Logger {
 
     /// Update the cached levels when configuration changes
     fn refresh_effective_levels(state: &mut SharedState) {
-        let min_required_severity = Self::compute_min_severity(&state.options);
-        state.min_required_severity = min_required_severity;
+        let options = &state.options;
+
+        // Transports may have been added, or the sink replaced
+        for (_handle, transport) in options.transports.iter().flatten() {
+            transport.attach_reporting(options.diagnostics.as_ref(), &state.stats);
+        }
+
+        // Like an `off` target level, an `off` logger level overrides the transports' levels
+        let default_threshold = match options.level.as_deref() {
+            Some(directives::OFF) => Threshold::Off,
+            _ => Threshold::Max(Self::compute_min_severity(options)),
+        };
+
+        // Target levels whose level is unknown are skipped, like an unknown logger level
+        let mut target_levels: Vec<_> = options
+            .target_levels
+            .iter()
+            .flatten()
+            .filter_map(|(target, level)| {
+                let threshold = if level == directives::OFF {
+                    Threshold::Off
+                } else {
+                    options.levels.as_ref()?.get_severity(level)?;
+                    Threshold::Max(Self::compute_min_severity_at(options, Some(level)))
+                };
+                Some(TargetLevel {
+                    target: target.clone(),
+                    level: level.clone(),
+                    threshold,
+                })
+            })
+            .collect();
+        target_levels.sort_by_key(|target_level| std::cmp::Reverse(target_level.target.len()));
+
+        // Entries at a level are enabled if any target, or the default, accepts them
+        let min_required =
+            target_levels
+                .iter()
+                .fold(default_threshold, |min_required, target_level| {
+                    match (min_required, target_level.threshold) {
+                        (threshold, Threshold::Off) | (Threshold::Off, threshold) => threshold,
+                        (Threshold::Max(Some(current)), Threshold::Max(Some(severity))) => {
+                            Threshold::Max(Some(current.max(severity)))
+                        }
+                        _ => Threshold::Max(None),
+                    }
+                });
+
+        state.default_threshold = default_threshold;
+        state.target_levels = target_levels;
+        state.min_required = min_required;
     }
 
     fn worker_loop(
-        receiver: Arc<Receiver<LogMessage>>,
+        mut receiver: Arc<Receiver<LogMessage>>,
+        mut priority: Receiver<Arc<LogInfo>>,
         shared_state: Arc<RwLock<SharedState>>,
         buffer: Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
-        flush_complete: Arc<(Mutex<bool>, Condvar)>,
+        #[cfg(feature = "async")] capacity_waiters: Arc<CapacityWaiters>,
     ) {
-        for message in receiver.iter() {
+        // A control message that cut a batch short, handled next
+        let mut pending = None;
+        // The channel to switch to once the current one is drained
+        let mut resized = None;
+        #[cfg(feature = "config")]
+        let mut watch: Option<Box<ConfigWatch>> = None;
+
+        loop {
+            #[cfg(feature = "config")]
+            if let Some(watch) = &mut watch {
+                Self::reload_config(watch, &shared_state, &buffer);
+            }
+
+            // With a spill file in use, wake up periodically to replay it even when idle
+            let spool = shared_state.read().spool.clone();
+            let mut timeout = spool.as_ref().map(|_| SPOOL_POLL_INTERVAL);
+            // Wake up when a repeat window closes to write its summary
+            if let Some(until_due) = Self::until_next_summary(&shared_state.read()) {
+                timeout = Some(timeout.map_or(until_due, |t| t.min(until_due)));
+            }
+            #[cfg(feature = "config")]
+            if let Some(watch) = &watch {
+                let until_due = watch.until_due();
+                timeout = Some(timeout.map_or(until_due, |t| t.min(until_due)));
+            }
+            let next = match pending.take() {
+                Some(message) => Ok(message),
+                None => Self::next_message(&receiver, &priority, timeout),
+            };
+            let message = match next {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => {
+                    Self::write_summaries(&shared_state.read(), false);
+                    if let Some(spool) = &spool {
+                        Self::replay_spool(spool, &shared_state, &buffer);
+                        Self::retire_spool(&shared_state);
+                    }
+                    continue;
+                }
+                Err(RecvTimeoutError::Disconnected) => match resized.take() {
+                    // Loggers that picked up the old channel before a resize
+                    // have sent what they had, so move on to the new one
+                    Some((new_receiver, new_priority)) => {
+                        for entry in priority.iter() {
+                            Self::handle_entry(entry, &shared_state, &buffer);
+                        }
+                        receiver = new_receiver;
+                        priority = new_priority;
+                        continue;
+                    }
+                    None => break,
+                },
+            };
+
+            #[cfg(feature = "async")]
+            capacity_waiters.notify();
+
+            // Control messages wait for priority entries logged before them
+            if !matches!(message, LogMessage::Entry(_)) {
+                while let Ok(entry) = priority.try_recv() {
+                    Self::handle_entry(entry, &shared_state, &buffer);
+                }
+            }
+
             match message {
                 LogMessage::Entry(entry) => {
-                    // Use read lock to check if we have transports (allows parallelism)
-                    let has_transports = {
+                    let batching = {
                         let state = shared_state.read();
                         state
                             .options
-                            .transports
-                            .as_ref()
-                            .map_or(false, |t| !t.is_empty())
+                            .batch_size
+                            .filter(|&size| size > 1)
+                            .map(|size| (size, state.options.batch_latency.unwrap_or_default()))
                     };
 
-                    if !has_transports {
-                        // Only buffer lock needed here
-                        let mut buf = buffer.lock().unwrap();
-                        buf.push_back(Arc::clone(&entry));
-                        eprintln!(
-                            "[winston] Attempt to write logs with no transports, which can increase memory usage: {}",
-                            entry.message
-                        );
-                    } else {
-                        // Process any buffered entries first
-                        Self::process_buffered_entries(&shared_state, &buffer);
+                    match batching {
+                        Some((size, latency)) => {
+                            let (entries, next) =
+                                Self::collect_batch(entry, &receiver, &priority, size, latency);
+                            #[cfg(feature = "async")]
+                            capacity_waiters.notify();
+                            Self::handle_batch(entries, &shared_state, &buffer);
+                            pending = next;
+                        }
+                        None => Self::handle_entry(entry, &shared_state, &buffer),
+                    }
 
-                        // Process current entry with read lock (allows parallel processing)
-                        let state = shared_state.read();
-                        Self::process_entry(&entry, &state);
+                    if let Some(spool) = &spool {
+                        if receiver.is_empty() {
+                            Self::replay_spool(spool, &shared_state, &buffer);
+                        }
                     }
                 }
                 LogMessage::Configure(new_options) => {
-                    let mut state = shared_state.write();
-                    // Update only the provided options
-                    if let Some(level) = new_options.level {
-                        state.options.level = Some(level);
-                    }
-                    if let Some(levels) = new_options.levels {
-                        state.options.levels = Some(levels);
-                    }
-                    if let Some(transports) = new_options.transports {
-                        state.options.transports = Some(transports);
-                    }
-                    if let Some(format) = new_options.format {
-                        state.options.format = Some(format);
-                    }
-
-                    Self::refresh_effective_levels(&mut state);
-                    drop(state); // Release write lock before processing buffer
-
-                    // Process buffered entries with new configuration
-                    Self::process_buffered_entries(&shared_state, &buffer);
+                    Self::apply_options(*new_options, &shared_state, &buffer);
                 }
                 LogMessage::Shutdown => {
-                    Self::process_buffered_entries(&shared_state, &buffer);
+                    // Flush so transports with their own queues drain before close returns
+                    if let Err(e) = Self::flush_transports(&shared_state, &buffer) {
+                        shared_state.read().report(DiagnosticKind::FlushError, || e);
+                    }
                     break;
                 }
-                LogMessage::Flush => {
-                    let state = shared_state.read();
-
-                    if state
-                        .options
-                        .transports
-                        .as_ref()
-                        .map_or(false, |t| !t.is_empty())
-                    {
-                        drop(state); // Release read lock
-                        Self::process_buffered_entries(&shared_state, &buffer);
+                LogMessage::Flush(done) => {
+                    let _ = done.send(Self::flush_transports(&shared_state, &buffer));
+                }
+                #[cfg(feature = "async")]
+                LogMessage::FlushAsync(signal) => {
+                    signal.complete(Self::flush_transports(&shared_state, &buffer));
+                }
+                LogMessage::Resize {
+                    receiver: new_receiver,
+                    priority: new_priority,
+                } => {
+                    // Loggers may still be waiting to send on the old channel,
+                    // so it is drained until they are done
+                    resized = Some((new_receiver, new_priority));
+                }
+                #[cfg(feature = "config")]
+                LogMessage::Watch(new_watch) => watch = new_watch,
+            }
+        }
+    }
 
-                        let state = shared_state.read();
-                        if let Some(transports) = &state.options.transports {
-                            for (_handle, transport) in transports {
-                                let _ = transport.get_transport().flush();
-                            }
-                        }
+    /// Applies the options that are set, keeping the current value of the rest.
+    fn apply_options(
+        new_options: LoggerOptions,
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) {
+        let mut state = shared_state.write();
+        if let Some(level) = new_options.level {
+            state.options.level = Some(level);
+        }
+        if let Some(levels) = new_options.levels {
+            state.options.levels = Some(levels);
+        }
+        if let Some(target_levels) = new_options.target_levels {
+            state.options.target_levels = Some(target_levels);
+        }
+        if let Some(transports) = new_options.transports {
+            state.options.transports = Some(transports);
+        }
+        if let Some(format) = new_options.format {
+            state.options.format = Some(format);
+        }
+        if let Some(default_meta) = new_options.default_meta {
+            state.options.default_meta = Some(default_meta);
+        }
+        if let Some(exception_handlers) = new_options.exception_handlers {
+            state.options.exception_handlers = Some(exception_handlers);
+            Self::install_configured_panic_hook(&mut state, shared_state);
+        }
+        if let Some(filter) = new_options.filter {
+            state.options.filter = Some(filter);
+        }
+        if let Some(limiter) = new_options.rate_limit {
+            state.options.rate_limit = Some(limiter);
+        }
+        if let Some(sampler) = new_options.sampling {
+            state.options.sampling = Some(sampler);
+        }
+        if let Some(on_error) = new_options.on_error {
+            state.options.on_error = Some(on_error);
+        }
+        if let Some(diagnostics) = new_options.diagnostics {
+            state.options.diagnostics = Some(diagnostics);
+        }
+        if let Some(capacity) = new_options.buffer_capacity {
+            state.options.buffer_capacity = Some(capacity);
+        }
+        if let Some(policy) = new_options.buffer_overflow {
+            state.options.buffer_overflow = Some(policy);
+        }
+        if let Some(level) = new_options.priority_level {
+            state.options.priority_level = Some(level);
+        }
+        if let Some(size) = new_options.batch_size {
+            state.options.batch_size = Some(size);
+        }
+        if let Some(latency) = new_options.batch_latency {
+            state.options.batch_latency = Some(latency);
+        }
+        if let Some(strategy) = new_options.backpressure_strategy {
+            Self::apply_backpressure_strategy(&mut state, strategy);
+        }
+
+        Self::refresh_effective_levels(&mut state);
+        drop(state); // Release write lock before processing buffer
+
+        // Process buffered entries with new configuration
+        Self::process_buffered_entries(shared_state, buffer);
+    }
+
+    /// Checks a watched config file and applies it if it has changed.
+    ///
+    /// Transports being replaced are flushed first, so what was already
+    /// written to them is not lost. If the file can't be loaded, the current
+    /// configuration stays in place.
+    #[cfg(feature = "config")]
+    fn reload_config(
+        watch: &mut ConfigWatch,
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) {
+        let check = |options: &LoggerOptions| {
+            let state = shared_state.read();
+            let Some(levels) = options.levels.as_ref().or(state.options.levels.as_ref()) else {
+                return Ok(());
+            };
+            config::check_levels(options, levels)?;
+            match (&options.level, &state.options.level) {
+                (None, Some(level)) if levels.get_severity(level).is_none() => Err(format!(
+                    "levels: the current level {:?} is not defined",
+                    level
+                )),
+                _ => Ok(()),
+            }
+        };
+        match watch.poll(check) {
+            None => {}
+            Some(Ok(ConfigChange { options, removed })) => {
+                if options.transports.is_some() {
+                    if let Err(e) = Self::flush_transports(shared_state, buffer) {
+                        shared_state.read().report(DiagnosticKind::FlushError, || e);
                     }
+                }
+                Self::apply_options(options, shared_state, buffer);
+                if !removed.is_empty() {
+                    shared_state.read().report(DiagnosticKind::Config, || {
+                        format!(
+                            "{}: removed keys are not applied, keeping their current value: {}",
+                            watch.path().display(),
+                            removed.join(", ")
+                        )
+                    });
+                }
+            }
+            Some(Err(e)) => shared_state.read().report(DiagnosticKind::Config, || {
+                format!(
+                    "Failed to reload {}, keeping the current configuration: {}",
+                    watch.path().display(),
+                    e
+                )
+            }),
+        }
+    }
+
+    /// Takes the next message, preferring entries waiting in the priority lane.
+    ///
+    /// Without a timeout this blocks until a message arrives.
+    fn next_message(
+        receiver: &Receiver<LogMessage>,
+        priority: &Receiver<Arc<LogInfo>>,
+        timeout: Option<Duration>,
+    ) -> Result<LogMessage, RecvTimeoutError> {
+        if let Ok(entry) = priority.try_recv() {
+            return Ok(LogMessage::Entry(entry));
+        }
+
+        // A resize may close the old priority lane before the old channel, so
+        // carry on with the channel alone
+        let disconnected = |_| RecvTimeoutError::Disconnected;
+        match timeout {
+            Some(timeout) => select! {
+                recv(priority) -> entry => match entry {
+                    Ok(entry) => Ok(LogMessage::Entry(entry)),
+                    Err(_) => receiver.recv_timeout(timeout),
+                },
+                recv(receiver) -> message => message.map_err(disconnected),
+                default(timeout) => Err(RecvTimeoutError::Timeout),
+            },
+            None => select! {
+                recv(priority) -> entry => match entry {
+                    Ok(entry) => Ok(LogMessage::Entry(entry)),
+                    Err(_) => receiver.recv().map_err(disconnected),
+                },
+                recv(receiver) -> message => message.map_err(disconnected),
+            },
+        }
+    }
+
+    /// Gathers up to `size` entries, starting with `first`, waiting at most
+    /// `latency` for more to arrive.
+    ///
+    /// Returns the entries and the control message that ended the batch early, if any.
+    fn collect_batch(
+        first: Arc<LogInfo>,
+        receiver: &Receiver<LogMessage>,
+        priority: &Receiver<Arc<LogInfo>>,
+        size: usize,
+        latency: Duration,
+    ) -> (Vec<Arc<LogInfo>>, Option<LogMessage>) {
+        let deadline = Instant::now() + latency;
+        let mut entries = vec![first];
+
+        while entries.len() < size {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match Self::next_message(receiver, priority, Some(remaining)) {
+                Ok(LogMessage::Entry(entry)) => entries.push(entry),
+                Ok(control) => return (entries, Some(control)),
+                // A disconnect is picked up by the worker loop on its next receive
+                Err(_) => break,
+            }
+        }
+
+        (entries, None)
+    }
+
+    /// Writes a batch of entries, handing each transport its share in one
+    /// [`log_batch`](Transport::log_batch) call.
+    fn handle_batch(
+        entries: Vec<Arc<LogInfo>>,
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) {
+        let has_transports = shared_state
+            .read()
+            .options
+            .transports
+            .as_ref()
+            .is_some_and(|t| !t.is_empty());
+
+        if !has_transports {
+            let state = shared_state.read();
+            for entry in entries {
+                Self::buffer_entry(entry, &state, buffer);
+            }
+            return;
+        }
+
+        Self::process_buffered_entries(shared_state, buffer);
+
+        let state = shared_state.read();
+        let mut output = Output::Batched(HashMap::new());
+        for entry in &entries {
+            Self::route_entry(entry, &state, &mut output);
+        }
+
+        let (Output::Batched(mut batches), Some(transports)) = (output, &state.options.transports)
+        else {
+            return;
+        };
+        for (handle, transport) in transports {
+            if let Some(batch) = batches.remove(handle) {
+                let count = batch.len() as u64;
+                transport.get_transport().log_batch(batch);
+                state.stats.record_deliveries(*handle, count);
+            }
+        }
+    }
+
+    /// Writes an entry to the transports, or buffers it if there are none.
+    fn handle_entry(
+        entry: Arc<LogInfo>,
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) {
+        // Use read lock to check if we have transports (allows parallelism)
+        let has_transports = {
+            let state = shared_state.read();
+            state
+                .options
+                .transports
+                .as_ref()
+                .is_some_and(|t| !t.is_empty())
+        };
+
+        if !has_transports {
+            let state = shared_state.read();
+            Self::buffer_entry(entry, &state, buffer);
+        } else {
+            // Process any buffered entries first
+            Self::process_buffered_entries(shared_state, buffer);
+
+            // Process current entry with read lock (allows parallel processing)
+            let state = shared_state.read();
+            Self::process_entry(&entry, &state);
+        }
+    }
+
+    /// Writes out entries spilled to disk while the channel was full.
+    fn replay_spool(
+        spool: &Spool,
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) {
+        if !spool.has_pending() {
+            return;
+        }
+
+        match spool.take() {
+            Ok(entries) => {
+                for entry in entries {
+                    Self::handle_entry(Arc::new(entry), shared_state, buffer);
+                }
+            }
+            Err(e) => shared_state.read().report(DiagnosticKind::Internal, || {
+                format!(
+                    "Failed to replay spill file {}: {}",
+                    spool.path().display(),
+                    e
+                )
+            }),
+        }
+    }
+
+    /// Drops the spill file once the strategy no longer uses it and it has been replayed.
+    fn retire_spool(shared_state: &Arc<RwLock<SharedState>>) {
+        let mut state = shared_state.write();
+        let spilling = matches!(
+            state.options.backpressure_strategy,
+            Some(BackpressureStrategy::SpillToFile(_))
+        );
+        if !spilling
+            && state
+                .spool
+                .as_ref()
+                .is_some_and(|spool| !spool.has_pending())
+        {
+            state.spool = None;
+        }
+    }
+
+    /// Writes out buffered entries and flushes every transport, if any are configured.
+    ///
+    /// Each failure is reported to the `on_error` handler; the returned error
+    /// names every transport that failed.
+    fn flush_transports(
+        shared_state: &Arc<RwLock<SharedState>>,
+        buffer: &Arc<Mutex<VecDeque<Arc<LogInfo>>>>,
+    ) -> Result<(), String> {
+        let spool = shared_state.read().spool.clone();
+        if let Some(spool) = spool {
+            Self::replay_spool(&spool, shared_state, buffer);
+        }
 
-                    let (lock, cvar) = &*flush_complete;
-                    let mut completed = lock.lock().unwrap();
-                    *completed = true;
-                    cvar.notify_one();
+        let state = shared_state.read();
+
+        if state
+            .options
+            .transports
+            .as_ref()
+            .is_none_or(|t| t.is_empty())
+        {
+            return Ok(());
+        }
+
+        drop(state); // Release read lock
+        Self::process_buffered_entries(shared_state, buffer);
+
+        let state = shared_state.read();
+        Self::write_summaries(&state, true);
+
+        let mut failures = Vec::new();
+        if let Some(transports) = &state.options.transports {
+            for (handle, transport) in transports {
+                if let Err(e) = transport.get_transport().flush() {
+                    if let Some(on_error) = &state.options.on_error {
+                        on_error(*handle, &e);
+                    }
+                    failures.push(format!("{}: {}", handle, e));
                 }
             }
         }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Failed to flush {} transport(s): {}",
+                failures.len(),
+                failures.join("; ")
+            ))
+        }
+    }
+
+    /// Holds an entry until a transport is added, applying the buffer's
+    /// capacity and overflow policy.
+    fn buffer_entry(
+        entry: Arc<LogInfo>,
+        state: &SharedState,
+        buffer: &Mutex<VecDeque<Arc<LogInfo>>>,
+    ) {
+        StatsCounters::increment(&state.stats.buffered);
+        let capacity = state.options.buffer_capacity.unwrap_or(1024);
+        let policy = state
+            .options
+            .buffer_overflow
+            .unwrap_or(BufferOverflowPolicy::DropOldest);
+
+        let mut buf = buffer.lock().unwrap();
+        if buf.is_empty() {
+            state.report(DiagnosticKind::NoTransport, || {
+                format!(
+                    "No transports configured; buffering up to {} entries until one is added.",
+                    capacity
+                )
+            });
+        }
+
+        buf.push_back(entry);
+        if buf.len() <= capacity {
+            if buf.len() == capacity {
+                state.report(DiagnosticKind::Drop, || {
+                    format!(
+                        "Log buffer is full ({} entries); overflow policy is {:?}.",
+                        capacity, policy
+                    )
+                });
+            }
+            return;
+        }
+
+        StatsCounters::increment(&state.stats.buffer_overflow);
+        let evicted = match policy {
+            BufferOverflowPolicy::DropNewest => buf.pop_back(),
+            BufferOverflowPolicy::DropOldest | BufferOverflowPolicy::SpillToStderr => {
+                buf.pop_front()
+            }
+        };
+        drop(buf);
+
+        if let (BufferOverflowPolicy::SpillToStderr, Some(evicted)) = (policy, evicted) {
+            let evicted = Arc::unwrap_or_clone(evicted);
+            let formatted = match &state.options.format {
+                Some(format) => format.transform(evicted),
+                None => Some(evicted),
+            };
+            if let Some(formatted) = formatted {
+                eprintln!("{}", formatted);
+            }
+        }
     }
 
     fn process_buffered_entries(
@@ -276,65 +953,341 @@ impl Logger {
         }
     }
 
-    fn process_entry(entry: &Arc<LogInfo>, state: &SharedState) {
+    pub(crate) fn process_entry(entry: &Arc<LogInfo>, state: &SharedState) {
+        Self::route_entry(entry, state, &mut Output::Direct);
+    }
+
+    /// Applies the logger-wide metadata, filter and rate limit, then passes
+    /// the entry on to the transports through `output`.
+    fn route_entry(entry: &Arc<LogInfo>, state: &SharedState, output: &mut Output) {
         if entry.message.is_empty() && entry.meta.is_empty() {
             return;
         }
 
         let options = &state.options;
+
+        // Merge logger-wide metadata once; the entry's own fields take precedence
+        let merged;
+        let entry: &LogInfo = match &options.default_meta {
+            Some(default_meta) if !default_meta.is_empty() => {
+                let mut with_defaults = (**entry).clone();
+                for (key, value) in default_meta {
+                    with_defaults
+                        .meta
+                        .entry(key.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                merged = with_defaults;
+                &merged
+            }
+            _ => entry,
+        };
+
+        if let Some(filter) = &options.filter {
+            if !filter(entry) {
+                return;
+            }
+        }
+
+        match &options.rate_limit {
+            Some(limiter) => {
+                let verdict = limiter.check(entry);
+                for summary in limiter.take_summaries(false) {
+                    Self::deliver(&summary, state, output);
+                }
+                if Self::record_verdict(verdict, state) {
+                    Self::deliver(entry, state, output);
+                }
+            }
+            None => Self::deliver(entry, state, output),
+        }
+    }
+
+    /// Writes an entry to every transport that accepts it.
+    fn deliver(entry: &LogInfo, state: &SharedState, output: &mut Output) {
+        let options = &state.options;
+        let (target, module_path) = directives::entry_target(entry);
+        let logger_level = directives::find(&state.target_levels, target, module_path)
+            .map(|target_level| &target_level.level)
+            .or(options.level.as_ref());
+
         if let Some(transports) = &options.transports {
-            for (_handle, transport) in transports {
-                // Check if this transport cares about the level
-                let effective_level = transport.get_level().or_else(|| options.level.as_ref());
-
-                if let (Some(levels), Some(effective_level)) = (&options.levels, effective_level) {
-                    if let (Some(entry_sev), Some(required_sev)) = (
-                        levels.get_severity(&entry.level),
-                        levels.get_severity(effective_level),
-                    ) {
-                        if entry_sev > required_sev {
-                            continue; // skip: not enabled
+            for (handle, transport) in transports {
+                // An explicit level filter replaces the threshold check
+                if let Some(filter) = transport.get_level_filter() {
+                    if let Some(levels) = &options.levels {
+                        if !filter.accepts(&entry.level, levels) {
+                            continue;
+                        }
+                    }
+                } else {
+                    // Check if this transport cares about the level
+                    let effective_level = transport.get_level().or(logger_level);
+
+                    if let (Some(levels), Some(effective_level)) =
+                        (&options.levels, effective_level)
+                    {
+                        if let (Some(entry_sev), Some(required_sev)) = (
+                            levels.get_severity(&entry.level),
+                            levels.get_severity(effective_level),
+                        ) {
+                            if entry_sev > required_sev {
+                                continue; // skip: not enabled
+                            }
+                        } else {
+                            // If we can't get severity for either level, skip this transport
+                            continue;
                         }
-                    } else {
-                        // If we can't get severity for either level, skip this transport
-                        continue;
                     }
                 }
 
-                let formatted_message = match (transport.get_format(), &options.format) {
-                    (Some(tf), Some(_lf)) => tf.transform((**entry).clone()),
-                    (Some(tf), None) => tf.transform((**entry).clone()),
-                    (None, Some(lf)) => lf.transform((**entry).clone()),
-                    (None, None) => Some((**entry).clone()),
+                if !transport.accepts(entry) {
+                    continue;
+                }
+
+                let sampled;
+                let entry = match transport.get_sampling().map(|s| s.sample(entry)) {
+                    None | Some(Sample::Keep) => entry,
+                    Some(Sample::KeepAt(rate)) => {
+                        sampled = Sampler::with_rate(entry.clone(), rate);
+                        &sampled
+                    }
+                    Some(Sample::Drop) => {
+                        StatsCounters::increment(&state.stats.sampled_out);
+                        continue;
+                    }
                 };
-                if let Some(msg) = formatted_message {
+
+                match transport.get_rate_limit() {
+                    Some(limiter) => {
+                        let verdict = limiter.check(entry);
+                        for summary in limiter.take_summaries(false) {
+                            Self::write_to_transport(&summary, *handle, transport, state, output);
+                        }
+                        if Self::record_verdict(verdict, state) {
+                            Self::write_to_transport(entry, *handle, transport, state, output);
+                        }
+                    }
+                    None => Self::write_to_transport(entry, *handle, transport, state, output),
+                }
+            }
+        }
+    }
+
+    /// Counts entries a rate limiter held back, returning `true` if `verdict` admits the entry.
+    fn record_verdict(verdict: Verdict, state: &SharedState) -> bool {
+        match verdict {
+            Verdict::Admit => return true,
+            Verdict::Repeated => StatsCounters::increment(&state.stats.suppressed_repeats),
+            Verdict::Limited => StatsCounters::increment(&state.stats.rate_limited),
+        }
+        false
+    }
+
+    fn write_to_transport(
+        entry: &LogInfo,
+        handle: TransportHandle,
+        transport: &LoggerTransport<LogInfo>,
+        state: &SharedState,
+        output: &mut Output,
+    ) {
+        let formatted_message = match (transport.get_format(), &state.options.format) {
+            (Some(tf), Some(_lf)) => tf.transform(entry.clone()),
+            (Some(tf), None) => tf.transform(entry.clone()),
+            (None, Some(lf)) => lf.transform(entry.clone()),
+            (None, None) => Some(entry.clone()),
+        };
+        if let Some(msg) = formatted_message {
+            match output {
+                Output::Direct => {
                     transport.get_transport().log(msg);
+                    state.stats.record_delivery(handle);
                 }
+                Output::Batched(batches) => batches.entry(handle).or_default().push(msg),
             }
         }
     }
 
-    fn is_level_enabled(entry_level: &str, state: &SharedState) -> bool {
-        if let Some(min_required) = state.min_required_severity {
-            if let Some(levels) = &state.options.levels {
-                if let Some(entry_severity) = levels.get_severity(entry_level) {
-                    return min_required >= entry_severity;
+    /// Writes out the repeats whose window has closed, or every repeat the
+    /// rate limiters are still holding back when `flush` is set.
+    fn write_summaries(state: &SharedState, flush: bool) {
+        if let Some(limiter) = &state.options.rate_limit {
+            for summary in limiter.take_summaries(flush) {
+                Self::deliver(&summary, state, &mut Output::Direct);
+            }
+        }
+        if let Some(transports) = &state.options.transports {
+            for (handle, transport) in transports {
+                if let Some(limiter) = transport.get_rate_limit() {
+                    for summary in limiter.take_summaries(flush) {
+                        Self::write_to_transport(
+                            &summary,
+                            *handle,
+                            transport,
+                            state,
+                            &mut Output::Direct,
+                        );
+                    }
                 }
             }
         }
-        false
+    }
+
+    /// Returns how long until a rate limiter has a repeat summary to write.
+    fn until_next_summary(state: &SharedState) -> Option<Duration> {
+        let transports = state.options.transports.iter().flatten();
+        state
+            .options
+            .rate_limit
+            .iter()
+            .chain(transports.filter_map(|(_, transport)| transport.get_rate_limit()))
+            .filter_map(RateLimiter::until_next_summary)
+            .min()
+    }
+
+    fn is_level_enabled(entry_level: &str, state: &SharedState) -> bool {
+        Self::is_within(entry_level, state.min_required, state)
+    }
+
+    /// Like [`is_level_enabled`](Self::is_level_enabled), for an entry from
+    /// `target` or `module_path`.
+    fn is_target_enabled(
+        entry_level: &str,
+        target: Option<&str>,
+        module_path: Option<&str>,
+        state: &SharedState,
+    ) -> bool {
+        let threshold = directives::find(&state.target_levels, target, module_path)
+            .map_or(state.default_threshold, |target_level| {
+                target_level.threshold
+            });
+        Self::is_within(entry_level, threshold, state)
+    }
+
+    fn is_within(entry_level: &str, threshold: Threshold, state: &SharedState) -> bool {
+        let Threshold::Max(threshold) = threshold else {
+            return false;
+        };
+        let Some(levels) = &state.options.levels else {
+            // No level table means the worker does not filter by level either
+            return true;
+        };
+        let Some(entry_severity) = levels.get_severity(entry_level) else {
+            return false;
+        };
+        threshold.is_none_or(|min_required| min_required >= entry_severity)
+    }
+
+    /// Returns `true` if an entry at `level` would be accepted by the logger or
+    /// at least one of its transports. With [target levels](LoggerOptions::target_level),
+    /// this is `true` if an entry from any target would be.
+    ///
+    /// [`log`](Self::log) already performs this check, so calling it first is
+    /// only worthwhile when building the entry or its metadata is expensive.
+    ///
+    /// # Example
+    /// ```ignore
+    /// if logger.is_enabled("debug") {
+    ///     logger.log(LogInfo::new("debug", "State").with_meta("dump", expensive_dump()));
+    /// }
+    /// ```
+    pub fn is_enabled(&self, level: &str) -> bool {
+        let state = self.inner.shared_state.read();
+        Self::is_level_enabled(level, &state)
+    }
+
+    /// Checks `entry` against the level thresholds and samples it, counting it
+    /// as accepted, filtered or sampled out. Returns the entry to send, if any.
+    fn admit(&self, entry: LogInfo) -> Option<LogInfo> {
+        let state = self.inner.shared_state.read();
+        let (target, module_path) = directives::entry_target(&entry);
+        if !Self::is_target_enabled(&entry.level, target, module_path, &state) {
+            StatsCounters::increment(&self.inner.stats.filtered);
+            return None;
+        }
+
+        let entry = match &state.options.sampling {
+            Some(sampler) => sampler.apply(entry),
+            None => Some(entry),
+        };
+        match entry {
+            Some(_) => StatsCounters::increment(&self.inner.stats.accepted),
+            None => StatsCounters::increment(&self.inner.stats.sampled_out),
+        }
+        entry
+    }
+
+    /// Returns `true` if entries at `level` bypass backpressure through the priority lane.
+    fn is_priority(&self, level: &str) -> bool {
+        let state = self.inner.shared_state.read();
+        let (Some(levels), Some(priority_level)) =
+            (&state.options.levels, &state.options.priority_level)
+        else {
+            return false;
+        };
+        match (
+            levels.get_severity(level),
+            levels.get_severity(priority_level),
+        ) {
+            (Some(severity), Some(threshold)) => severity <= threshold,
+            _ => false,
+        }
+    }
+
+    /// Queues an entry on the priority lane, blocking while the lane is full.
+    fn send_priority(&self, entry: Arc<LogInfo>) {
+        StatsCounters::increment(&self.inner.stats.prioritized);
+        // Not holding the channel lock while blocked, so a resize isn't held up
+        let priority_sender = self.inner.channel.read().priority_sender.clone();
+        if priority_sender.send(entry).is_err() {
+            self.report_disconnected();
+        }
+    }
+
+    /// Returns copies of the entries held while no transport is configured.
+    ///
+    /// Entries still waiting in the channel are processed first.
+    pub fn buffered_entries(&self) -> Vec<LogInfo> {
+        let _ = self.flush();
+        let buffer = self.inner.buffer.lock().unwrap();
+        buffer.iter().map(|entry| (**entry).clone()).collect()
+    }
+
+    /// Removes and returns the entries held while no transport is configured.
+    ///
+    /// Useful for startup logging: log before configuration is loaded, then
+    /// drain the buffer and decide where those entries should go. Entries
+    /// still waiting in the channel are processed first.
+    pub fn drain_buffer(&self) -> Vec<LogInfo> {
+        let _ = self.flush();
+        let mut buffer = self.inner.buffer.lock().unwrap();
+        buffer.drain(..).map(Arc::unwrap_or_clone).collect()
+    }
+
+    /// Returns a snapshot of the logger's counters and queue sizes.
+    ///
+    /// Useful for tuning [`channel_capacity`](LoggerOptions::channel_capacity)
+    /// and the backpressure strategy: a steadily non-zero `channel_depth` or
+    /// growing drop counts mean the worker can't keep up.
+    pub fn stats(&self) -> LoggerStats {
+        let buffer_len = self.inner.buffer.lock().unwrap().len();
+        let channel_depth = {
+            let channel = self.inner.channel.read();
+            channel.sender.len() + channel.priority_sender.len()
+        };
+        self.inner.stats.snapshot(channel_depth, buffer_len)
     }
 
     pub fn query(&self, options: &LogQuery) -> Result<Vec<LogInfo>, String> {
-        let state = self.shared_state.read();
-        let buffer = self.buffer.lock().unwrap();
+        let state = self.inner.shared_state.read();
+        let buffer = self.inner.buffer.lock().unwrap();
         let mut results = Vec::new();
 
         // First, query the buffered entries
         results.extend(
             buffer
                 .iter()
-                .filter(|entry| options.matches(&***entry))
+                .filter(|entry| options.matches(entry))
                 .map(|arc| (**arc).clone())
                 .collect::<Vec<_>>(),
         );
@@ -352,149 +1305,544 @@ impl Logger {
         Ok(results)
     }
 
-    pub fn log(&self, entry: LogInfo) {
-        let entry = Arc::new(entry);
-        match self.sender.try_send(LogMessage::Entry(entry)) {
-            Ok(_) => {}
-            Err(TrySendError::Full(LogMessage::Entry(entry))) => {
-                self.handle_full_channel(entry);
-            }
-            Err(TrySendError::Full(LogMessage::Configure(config))) => {
-                eprintln!("[winston] Channel is full, forcing config update.");
-                let _ = self.sender.send(LogMessage::Configure(config));
-            }
-            Err(TrySendError::Full(LogMessage::Shutdown)) => {
-                eprintln!("[winston] Channel is full, forcing shutdown.");
-                let _ = self.sender.send(LogMessage::Shutdown);
-            }
-            Err(TrySendError::Full(LogMessage::Flush)) => {
-                eprintln!("[winston] Channel is full, forcing flush.");
-                let _ = self.sender.send(LogMessage::Flush);
-            }
-            Err(TrySendError::Disconnected(_)) => {
-                eprintln!("[winston] Channel is disconnected. Unable to log message.");
-            }
+    pub fn log(&self, entry: LogInfo) {
+        // Drop entries no transport would accept before touching the channel
+        let Some(entry) = self.admit(entry) else {
+            return;
+        };
+
+        let entry = Arc::new(entry);
+        if self.is_priority(&entry.level) {
+            self.send_priority(entry);
+            return;
+        }
+
+        match self.try_send(LogMessage::Entry(entry)) {
+            Ok(_) => {}
+            Err(TrySendError::Full(LogMessage::Entry(entry))) => {
+                self.handle_full_channel(entry);
+            }
+            Err(TrySendError::Full(_)) => unreachable!("only entries are sent here"),
+            Err(TrySendError::Disconnected(_)) => self.report_disconnected(),
+        }
+    }
+
+    fn report(&self, kind: DiagnosticKind, message: impl FnOnce() -> String) {
+        self.inner.shared_state.read().report(kind, message);
+    }
+
+    fn report_disconnected(&self) {
+        self.report(DiagnosticKind::Disconnected, || {
+            "Channel is disconnected. Unable to log message.".to_string()
+        });
+    }
+
+    pub fn logi(&self, entry: LogInfo) {
+        let Some(entry) = self.admit(entry) else {
+            return;
+        };
+
+        let entry = Arc::new(entry);
+        let _ = self.send(LogMessage::Entry(entry));
+    }
+
+    // The channel lock is released before these return, so callers can't
+    // hold it while handling a full or disconnected channel
+    fn try_send(&self, message: LogMessage) -> Result<(), TrySendError<LogMessage>> {
+        self.inner.channel.read().sender.try_send(message)
+    }
+
+    // Blocks on a copy of the sender rather than under the channel lock,
+    // which a resize would have to wait for, and every logger behind it.
+    // The worker drains the old channel until such copies are dropped.
+    fn sender(&self) -> Sender<LogMessage> {
+        self.inner.channel.read().sender.clone()
+    }
+
+    fn send(&self, message: LogMessage) -> Result<(), SendError<LogMessage>> {
+        self.sender().send(message)
+    }
+
+    fn backpressure_strategy(&self) -> BackpressureStrategy {
+        let state = self.inner.shared_state.read();
+        state
+            .options
+            .backpressure_strategy
+            .clone()
+            .unwrap_or(BackpressureStrategy::Block)
+    }
+
+    /// Handles backpressure strategies when the channel is full.
+    fn handle_full_channel(&self, entry: Arc<LogInfo>) {
+        match self.backpressure_strategy() {
+            BackpressureStrategy::DropOldest => {
+                self.drop_oldest_and_retry(entry);
+            }
+            BackpressureStrategy::Block => {
+                // Block until the channel has space
+                let _ = self.send(LogMessage::Entry(entry));
+            }
+            BackpressureStrategy::DropCurrent => {
+                StatsCounters::increment(&self.inner.stats.dropped_current);
+                self.report(DiagnosticKind::Drop, || {
+                    format!(
+                        "Dropping current log entry due to full channel: {}",
+                        entry.message
+                    )
+                });
+            }
+            BackpressureStrategy::BlockTimeout(timeout) => {
+                let sent = self
+                    .sender()
+                    .send_timeout(LogMessage::Entry(entry), timeout);
+                match sent {
+                    Ok(()) => {}
+                    Err(SendTimeoutError::Timeout(message)) => {
+                        StatsCounters::increment(&self.inner.stats.dropped_timeout);
+                        self.report(DiagnosticKind::Drop, || match message {
+                            LogMessage::Entry(entry) => format!(
+                                "Dropping log entry after waiting {:?} for channel space: {}",
+                                timeout, entry.message
+                            ),
+                            _ => unreachable!("only entries are sent here"),
+                        });
+                    }
+                    Err(SendTimeoutError::Disconnected(_)) => self.report_disconnected(),
+                }
+            }
+            BackpressureStrategy::Sample(n) => {
+                let seen = self.inner.sample_counter.fetch_add(1, Ordering::Relaxed);
+                if n <= 1 || seen.is_multiple_of(u64::from(n)) {
+                    let _ = self.send(LogMessage::Entry(entry));
+                } else {
+                    StatsCounters::increment(&self.inner.stats.dropped_sampled);
+                    self.report(DiagnosticKind::Drop, || {
+                        format!(
+                            "Sampling 1 in {} entries while the channel is full. Dropping: {}",
+                            n, entry.message
+                        )
+                    });
+                }
+            }
+            BackpressureStrategy::SpillToFile(_) => self.spill(entry),
+        }
+    }
+
+    /// Appends an entry to the spill file for the worker to replay later.
+    fn spill(&self, entry: Arc<LogInfo>) {
+        let Some(spool) = self.inner.shared_state.read().spool.clone() else {
+            // No spill file was set up at construction; wait for room instead
+            let _ = self.send(LogMessage::Entry(entry));
+            return;
+        };
+
+        match spool.write(&entry) {
+            Ok(()) => StatsCounters::increment(&self.inner.stats.spilled),
+            Err(e) => {
+                StatsCounters::increment(&self.inner.stats.spill_failed);
+                self.report(DiagnosticKind::Drop, || {
+                    format!(
+                        "Failed to write to spill file {}: {}. Dropping: {}",
+                        spool.path().display(),
+                        e,
+                        entry.message
+                    )
+                });
+            }
+        }
+    }
+
+    /// Drops the oldest log message from the channel and attempts to send the new one.
+    fn drop_oldest_and_retry(&self, entry: Arc<LogInfo>) {
+        // Hold the channel throughout so a resize can't move requeued messages
+        // to a different channel; nothing below blocks
+        let channel = self.inner.channel.read();
+
+        // Control messages are never dropped. Any popped on the way to the
+        // oldest entry are put back behind the pending entries, dropping
+        // further entries if other threads take the freed slots first.
+        let mut controls = VecDeque::new();
+        let mut need_room = true;
+        while need_room || !controls.is_empty() {
+            if !need_room {
+                if let Some(control) = controls.pop_front() {
+                    match channel.sender.try_send(control) {
+                        Ok(()) | Err(TrySendError::Disconnected(_)) => continue,
+                        Err(TrySendError::Full(control)) => controls.push_front(control),
+                    }
+                }
+            }
+            match channel.receiver.try_recv() {
+                Ok(LogMessage::Entry(oldest)) => {
+                    need_room = false;
+                    StatsCounters::increment(&self.inner.stats.dropped_oldest);
+                    self.report(DiagnosticKind::Drop, || {
+                        format!(
+                            "Dropped oldest log entry due to full channel: {}",
+                            oldest.message
+                        )
+                    });
+                }
+                Ok(control) => controls.push_back(control),
+                Err(_) => need_room = false,
+            }
+        }
+
+        // Now try to send the new entry again
+        if let Err(e) = channel.sender.try_send(LogMessage::Entry(entry)) {
+            StatsCounters::increment(&self.inner.stats.dropped_oldest);
+            let dropped = e.into_inner();
+            self.report(DiagnosticKind::Drop, || match dropped {
+                LogMessage::Entry(entry) => format!(
+                    "Failed to log after dropping oldest. Dropping current message: {}",
+                    entry.message
+                ),
+                _ => "Failed to log after dropping oldest. Dropping current message.".to_string(),
+            });
+        }
+    }
+
+    pub fn close(&self) {
+        if self.inner.is_closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        self.unhandle_panics();
+
+        if let Err(e) = self.flush() {
+            self.report(DiagnosticKind::FlushError, || {
+                format!("Error flushing logs: {}", e)
+            });
+        }
+
+        let _ = self.send(LogMessage::Shutdown);
+
+        if let Ok(mut thread_handle) = self.inner.worker_thread.lock() {
+            if let Some(handle) = thread_handle.take() {
+                if let Err(e) = handle.join() {
+                    self.report(DiagnosticKind::Internal, || {
+                        format!("Error joining worker thread: {:?}", e)
+                    });
+                }
+            }
+        } else {
+            self.report(DiagnosticKind::Internal, || {
+                "Error acquiring lock on worker thread handle during close.".to_string()
+            });
+        }
+
+        self.release_pending_flushes();
+    }
+
+    /// Releases callers whose flush request arrived after the worker shut down.
+    fn release_pending_flushes(&self) {
+        let receiver = Arc::clone(&self.inner.channel.read().receiver);
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                // Dropping the acknowledgement sender wakes the waiting caller
+                LogMessage::Flush(_) => {}
+                #[cfg(feature = "async")]
+                LogMessage::FlushAsync(signal) => signal.complete(Ok(())),
+                _ => {}
+            }
+        }
+    }
+
+    /// Waits until every queued entry has been processed and all transports are flushed.
+    ///
+    /// Returns an error naming each transport whose `flush` failed.
+    pub fn flush(&self) -> Result<(), String> {
+        if self.inner.is_closed.load(Ordering::Acquire) {
+            return Ok(());
+        }
+
+        let (done_sender, done_receiver) = bounded(1);
+
+        // If send fails, worker is gone
+        if self.send(LogMessage::Flush(done_sender)).is_err() {
+            return Ok(());
+        }
+
+        // A receive error means the logger closed before handling this flush
+        done_receiver.recv().unwrap_or(Ok(()))
+    }
+
+    /// Installs a panic hook that logs uncaught panics.
+    ///
+    /// Each panic is written as an `error` entry carrying the panic message,
+    /// `location`, `thread` and a captured `backtrace`. The entry goes to the
+    /// configured [exception handlers](LoggerOptions::exception_handler), or to
+    /// the logger's transports when there are none, bypassing the worker queue.
+    /// Those transports are flushed before the previously installed hook runs
+    /// and the panic continues to unwind or abort.
+    ///
+    /// This is done automatically when exception handlers are configured,
+    /// whether through [`new`](Self::new), [`configure`](Self::configure) or a
+    /// watched config file. Calling this again while the hook is installed has
+    /// no effect.
+    pub fn handle_panics(&self) {
+        let mut state = self.inner.shared_state.write();
+        if state.panic_hook.is_none() {
+            state.panic_hook = Some(ExceptionHandler::install(Arc::downgrade(
+                &self.inner.shared_state,
+            )));
+        }
+    }
+
+    /// Stops logging panics through the hook installed by
+    /// [`handle_panics`](Self::handle_panics).
+    ///
+    /// The hook that was active before it is put back. If another hook has
+    /// been installed since, the logger's hook stays in the chain so that
+    /// hook keeps working, and just passes every panic on. This is also done
+    /// when the logger is closed.
+    pub fn unhandle_panics(&self) {
+        let handler = self.inner.shared_state.write().panic_hook.take();
+        if let Some(handler) = handler {
+            handler.uninstall();
+        }
+    }
+
+    /// Starts a timer that logs its duration when finished or dropped.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let timer = logger.start_timer();
+    /// run_query();
+    /// timer.done("Query finished"); // logs with a `duration_ms` meta field
+    /// ```
+    pub fn start_timer(&self) -> Profiler {
+        Profiler::new(ChildLogger::new(self.handle(), HashMap::new()))
+    }
+
+    /// Starts or stops the profile named `id`.
+    ///
+    /// The first call starts timing; the second call logs an `info` entry with
+    /// `id` as its message and the elapsed time as `duration_ms`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// logger.profile("db-query");
+    /// run_query();
+    /// logger.profile("db-query");
+    /// ```
+    pub fn profile(&self, id: impl Into<String>) {
+        let id = id.into();
+        let entry = LogInfo::new("info", id.clone());
+        self.profile_with(id, entry);
+    }
+
+    /// Like [`profile`](Self::profile), but logs `entry` when the profile stops.
+    ///
+    /// The entry is ignored when this call starts the profile.
+    pub fn profile_with(&self, id: impl Into<String>, entry: LogInfo) {
+        if let Some(elapsed) = self.toggle_profile(id.into()) {
+            self.log(profiler::with_duration(entry, elapsed));
         }
     }
 
-    pub fn logi(&self, entry: LogInfo) {
-        let entry = Arc::new(entry);
-        let _ = self.sender.send(LogMessage::Entry(entry));
+    /// Starts the profile `id`, or stops it and returns how long it ran.
+    pub(crate) fn toggle_profile(&self, id: String) -> Option<Duration> {
+        let mut profiles = self.inner.profiles.lock().unwrap();
+        match profiles.remove(&id) {
+            Some(start) => Some(start.elapsed()),
+            None => {
+                profiles.insert(id, Instant::now());
+                None
+            }
+        }
     }
 
-    /// Handles backpressure strategies when the channel is full.
-    fn handle_full_channel(&self, entry: Arc<LogInfo>) {
-        let strategy = {
-            let state = self.shared_state.read();
-            state
-                .options
-                .backpressure_strategy
-                .clone()
-                .unwrap_or(BackpressureStrategy::Block)
+    /// Async counterpart of [`log`](Self::log).
+    ///
+    /// With [`BackpressureStrategy::Block`], a full channel suspends the calling
+    /// task until the worker makes room instead of blocking the executor thread.
+    /// The other strategies behave exactly like `log`: `DropOldest` and
+    /// `DropCurrent` never wait, but [`BlockTimeout`](BackpressureStrategy::BlockTimeout)
+    /// waits for up to its timeout, [`Sample`](BackpressureStrategy::Sample)
+    /// waits for room for the entries it keeps, and
+    /// [`SpillToFile`](BackpressureStrategy::SpillToFile) waits for room when
+    /// its spill file couldn't be opened. Those waits block the executor thread.
+    #[cfg(feature = "async")]
+    pub async fn log_async(&self, entry: LogInfo) {
+        let Some(entry) = self.admit(entry) else {
+            return;
         };
 
-        match strategy {
-            BackpressureStrategy::DropOldest => {
-                self.drop_oldest_and_retry(entry);
-            }
-            BackpressureStrategy::Block => {
-                // Block until the channel has space
-                let _ = self.sender.send(LogMessage::Entry(entry));
-            }
-            BackpressureStrategy::DropCurrent => {
-                eprintln!(
-                    "[winston] Dropping current log entry due to full channel: {}",
-                    entry.message
-                );
+        let entry = Arc::new(entry);
+        if self.is_priority(&entry.level) {
+            StatsCounters::increment(&self.inner.stats.prioritized);
+            if !self.send_async(|c| &c.priority_sender, entry).await {
+                self.report_disconnected();
             }
+            return;
         }
-    }
 
-    /// Drops the oldest log message from the channel and attempts to send the new one.
-    fn drop_oldest_and_retry(&self, entry: Arc<LogInfo>) {
-        // Try to remove the oldest message from the channel using the shared receiver
-        if let Ok(oldest) = self.receiver.try_recv() {
-            eprintln!(
-                "[winston] Dropped oldest log entry due to full channel: {:?}",
-                oldest
-            );
+        match self.try_send(LogMessage::Entry(entry)) {
+            Ok(_) => {}
+            Err(TrySendError::Full(LogMessage::Entry(entry))) => {
+                if let BackpressureStrategy::Block = self.backpressure_strategy() {
+                    if !self
+                        .send_async(|c| &c.sender, LogMessage::Entry(entry))
+                        .await
+                    {
+                        self.report_disconnected();
+                    }
+                } else {
+                    self.handle_full_channel(entry);
+                }
+            }
+            Err(TrySendError::Full(_)) => unreachable!("only entries are sent here"),
+            Err(TrySendError::Disconnected(_)) => self.report_disconnected(),
         }
+    }
 
-        // Now try to send the new entry again
-        if let Err(e) = self.sender.try_send(LogMessage::Entry(entry)) {
-            eprintln!(
-                "[winston] Failed to log after dropping oldest. Dropping current message: {:?}",
-                e.into_inner()
-            );
+    /// Async counterpart of [`flush`](Self::flush) that awaits the worker's
+    /// acknowledgement instead of blocking the calling thread.
+    #[cfg(feature = "async")]
+    pub async fn flush_async(&self) -> Result<(), String> {
+        if self.inner.is_closed.load(Ordering::Acquire) {
+            return Ok(());
         }
+        self.flush_and_wait().await
     }
 
-    pub fn close(&self) {
-        if self.is_closed.swap(true, Ordering::SeqCst) {
+    /// Async counterpart of [`close`](Self::close).
+    ///
+    /// Flushes, shuts the worker down and awaits its exit. Joining the
+    /// finished worker thread afterwards does not block.
+    #[cfg(feature = "async")]
+    pub async fn close_async(&self) {
+        if self.inner.is_closed.swap(true, Ordering::SeqCst) {
             return;
         }
 
-        if let Err(e) = self.flush() {
-            eprintln!("Error flushing logs: {}", e);
+        self.unhandle_panics();
+        if let Err(e) = self.flush_and_wait().await {
+            self.report(DiagnosticKind::FlushError, || {
+                format!("Error flushing logs: {}", e)
+            });
         }
 
-        let _ = self.sender.send(LogMessage::Shutdown);
-
-        // Wake all threads waiting on flush BEFORE joining worker
-        {
-            let (lock, cvar) = &*self.flush_complete;
-            let mut completed = lock.lock().unwrap();
-            *completed = true; // Set to true so they don't wait again
-            cvar.notify_all(); // Wake ALL waiting threads
+        if self.send_async(|c| &c.sender, LogMessage::Shutdown).await {
+            self.inner.worker_finished.wait().await;
         }
 
-        if let Ok(mut thread_handle) = self.worker_thread.lock() {
-            if let Some(handle) = thread_handle.take() {
-                if let Err(e) = handle.join() {
-                    eprintln!("Error joining worker thread: {:?}", e);
-                }
+        if let Some(handle) = self.inner.worker_thread.lock().unwrap().take() {
+            if let Err(e) = handle.join() {
+                self.report(DiagnosticKind::Internal, || {
+                    format!("Error joining worker thread: {:?}", e)
+                });
             }
-        } else {
-            eprintln!("Error acquiring lock on worker thread handle during close.");
         }
+
+        self.release_pending_flushes();
     }
 
-    pub fn flush(&self) -> Result<(), String> {
-        if self.is_closed.load(Ordering::Acquire) {
-            return Ok(());
+    #[cfg(feature = "async")]
+    async fn flush_and_wait(&self) -> Result<(), String> {
+        let signal = Signal::new();
+        // If send fails, worker is gone
+        if self
+            .send_async(|c| &c.sender, LogMessage::FlushAsync(Arc::clone(&signal)))
+            .await
+        {
+            signal.wait().await
+        } else {
+            Ok(())
         }
+    }
 
-        let (lock, cvar) = &*self.flush_complete;
-        let mut completed = lock.lock().unwrap();
-        *completed = false;
+    /// Sends `message` on the sender picked by `lane`, suspending until it has room.
+    /// Returns `false` if the worker has disconnected.
+    ///
+    /// The sender is looked up on every attempt, so a resize while waiting is
+    /// picked up.
+    #[cfg(feature = "async")]
+    async fn send_async<T>(&self, lane: fn(&Channel) -> &Sender<T>, message: T) -> bool {
+        let mut message = Some(message);
+        std::future::poll_fn(|cx| {
+            let pending = message.take().expect("send_async polled after completion");
+            let sent = lane(&self.inner.channel.read()).try_send(pending);
+            match sent {
+                Ok(()) => return std::task::Poll::Ready(true),
+                Err(TrySendError::Disconnected(_)) => return std::task::Poll::Ready(false),
+                Err(TrySendError::Full(pending)) => message = Some(pending),
+            }
 
-        // If send fails, worker is gone
-        if self.sender.send(LogMessage::Flush).is_err() {
-            return Ok(());
-        }
+            self.inner.capacity_waiters.register(cx.waker());
 
-        while !*completed {
-            completed = cvar.wait(completed).unwrap();
-        }
+            // Retry in case the worker made room before the waker was registered
+            let sent = lane(&self.inner.channel.read()).try_send(message.take().unwrap());
+            match sent {
+                Ok(()) => std::task::Poll::Ready(true),
+                Err(TrySendError::Disconnected(_)) => std::task::Poll::Ready(false),
+                Err(TrySendError::Full(pending)) => {
+                    message = Some(pending);
+                    std::task::Poll::Pending
+                }
+            }
+        })
+        .await
+    }
 
-        Ok(())
+    /// Returns another handle to this logger that doesn't close it when dropped.
+    pub(crate) fn handle(&self) -> Logger {
+        Logger {
+            inner: Arc::clone(&self.inner),
+            owner: false,
+        }
     }
 
     pub fn builder() -> LoggerBuilder {
         LoggerBuilder::new()
     }
 
+    /// Creates a child logger that adds `meta` to every entry it logs.
+    ///
+    /// The child shares this logger's worker thread, transports and levels.
+    /// Metadata passed with an individual entry takes precedence over the
+    /// child's fields. The child is an owned handle, so it can be moved into
+    /// another thread or task; once this logger is closed or dropped, entries
+    /// logged through the child are discarded.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let request_logger = logger.child([("request_id", "abc-123")]);
+    /// log!(request_logger, info, "Handling request");
+    /// ```
+    pub fn child<I, K, V>(&self, meta: I) -> ChildLogger
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<serde_json::Value>,
+    {
+        ChildLogger::new(
+            self.handle(),
+            meta.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+
     /// Updates the logger configuration with new options, following this fallback chain:
     /// new options -> existing options -> defaults. Always clears existing transports
     /// and processes buffered entries after updating.
     ///
-    /// Note: The backpressure strategy and channel capacity are not reconfigured, as they are only used during logger creation.
+    /// A new channel capacity or backpressure strategy is applied as by
+    /// [`set_channel_capacity`](Self::set_channel_capacity) and
+    /// [`set_backpressure_strategy`](Self::set_backpressure_strategy).
+    ///
+    /// Options left unset keep their current value, so a filter, rate limit,
+    /// sampler, error handler, diagnostics sink or priority level is removed
+    /// with [`clear_filter`](Self::clear_filter) and the other `clear_` methods.
     ///
     /// # Arguments
     /// * `new_options` - Optional new configuration. If `None`, the existing configuration is retained.
     pub fn configure(&self, new_options: Option<LoggerOptions>) {
-        let mut state = self.shared_state.write();
+        let mut state = self.inner.shared_state.write();
         let default_options = LoggerOptions::default();
+        let mut new_capacity = None;
 
         if let Some(t) = state.options.transports.as_mut() {
             t.clear();
@@ -513,6 +1861,52 @@ impl Logger {
                 .level
                 .or_else(|| state.options.level.take().or(default_options.level));
 
+            state.options.target_levels = options
+                .target_levels
+                .or_else(|| state.options.target_levels.take());
+
+            state.options.default_meta = options
+                .default_meta
+                .or_else(|| state.options.default_meta.take());
+
+            state.options.exception_handlers = options
+                .exception_handlers
+                .or_else(|| state.options.exception_handlers.take());
+            Self::install_configured_panic_hook(&mut state, &self.inner.shared_state);
+
+            state.options.filter = options.filter.or_else(|| state.options.filter.take());
+
+            state.options.rate_limit = options
+                .rate_limit
+                .or_else(|| state.options.rate_limit.take());
+
+            state.options.sampling = options.sampling.or_else(|| state.options.sampling.take());
+
+            state.options.on_error = options.on_error.or_else(|| state.options.on_error.take());
+
+            state.options.diagnostics = options
+                .diagnostics
+                .or_else(|| state.options.diagnostics.take());
+
+            state.options.buffer_capacity =
+                options.buffer_capacity.or(state.options.buffer_capacity);
+
+            state.options.buffer_overflow =
+                options.buffer_overflow.or(state.options.buffer_overflow);
+
+            state.options.priority_level = options
+                .priority_level
+                .or_else(|| state.options.priority_level.take());
+
+            state.options.batch_size = options.batch_size.or(state.options.batch_size);
+
+            state.options.batch_latency = options.batch_latency.or(state.options.batch_latency);
+
+            if let Some(strategy) = options.backpressure_strategy {
+                Self::apply_backpressure_strategy(&mut state, strategy);
+            }
+            new_capacity = options.channel_capacity;
+
             // Add all transports we have been provided
             if let Some(transports) = options.transports {
                 state.options.transports = Some(transports);
@@ -522,8 +1916,197 @@ impl Logger {
         Self::refresh_effective_levels(&mut state);
         drop(state); // Release write lock
 
+        if let Some(capacity) = new_capacity {
+            self.set_channel_capacity(capacity);
+        }
+
         // Process buffered entries with new configuration
-        Self::process_buffered_entries(&self.shared_state, &self.buffer);
+        Self::process_buffered_entries(&self.inner.shared_state, &self.inner.buffer);
+    }
+
+    /// Changes how many messages the channel to the worker holds.
+    ///
+    /// Messages already queued are not lost: new messages go to a new channel
+    /// of the given capacity, and the worker switches over once it has
+    /// drained the old one. Loggers calling [`log`](Self::log) move to the new
+    /// channel at once; if the old channel is full, only this call waits until
+    /// the worker has room for the switch-over. The priority lane is resized too.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Widen the buffer while an incident is flooding the logs
+    /// logger.set_channel_capacity(100_000);
+    /// ```
+    pub fn set_channel_capacity(&self, capacity: usize) {
+        if self.inner.is_closed.load(Ordering::Acquire) {
+            return;
+        }
+
+        let mut channel = self.inner.channel.write();
+        if channel.sender.capacity() == Some(capacity) {
+            return;
+        }
+
+        let (sender, receiver) = bounded(capacity);
+        let (priority_sender, priority_receiver) = bounded(capacity);
+        let receiver = Arc::new(receiver);
+        // Only the worker's receiver is left on the old channel, so the send
+        // below fails rather than blocks if the worker has already exited
+        let Channel {
+            sender: old_sender, ..
+        } = std::mem::replace(
+            &mut *channel,
+            Channel {
+                sender,
+                receiver: Arc::clone(&receiver),
+                priority_sender,
+            },
+        );
+
+        // Senders are looked up through the lock, so from here on loggers
+        // move on to the new channel. Those still blocked on the old one
+        // finish there, as the worker drains it until every sender is gone.
+        drop(channel);
+
+        // Queued behind everything already sent, so the worker handles those
+        // first. Only this call waits if the old channel is full.
+        let _ = old_sender.send(LogMessage::Resize {
+            receiver,
+            priority: priority_receiver,
+        });
+        drop(old_sender);
+
+        self.inner.shared_state.write().options.channel_capacity = Some(capacity);
+    }
+
+    /// Changes what [`log`](Self::log) does when the channel is full.
+    ///
+    /// Switching to [`BackpressureStrategy::SpillToFile`] with a new path
+    /// moves any entries still waiting in the previous spill file to the new
+    /// one. After switching away from it, the worker keeps replaying the
+    /// spill file until it is empty.
+    pub fn set_backpressure_strategy(&self, strategy: BackpressureStrategy) {
+        let mut state = self.inner.shared_state.write();
+        Self::apply_backpressure_strategy(&mut state, strategy);
+    }
+
+    /// Removes the logger's filter, so every entry reaches the transports again.
+    pub fn clear_filter(&self) {
+        self.inner.shared_state.write().options.filter = None;
+    }
+
+    /// Removes the logger's rate limit. Repeats it was still holding back are
+    /// dropped without a summary.
+    pub fn clear_rate_limit(&self) {
+        self.inner.shared_state.write().options.rate_limit = None;
+    }
+
+    /// Removes the logger's sampler, so every entry is kept.
+    pub fn clear_sampling(&self) {
+        self.inner.shared_state.write().options.sampling = None;
+    }
+
+    /// Removes the handler that transport errors are passed to.
+    pub fn clear_on_error(&self) {
+        self.inner.shared_state.write().options.on_error = None;
+    }
+
+    /// Removes the diagnostics sink, so diagnostics go to stderr again.
+    pub fn clear_diagnostics(&self) {
+        let mut state = self.inner.shared_state.write();
+        state.options.diagnostics = None;
+        Self::refresh_effective_levels(&mut state);
+    }
+
+    /// Removes the priority level, so every entry goes through the channel.
+    pub fn clear_priority_level(&self) {
+        self.inner.shared_state.write().options.priority_level = None;
+    }
+
+    /// Polls a TOML or JSON config file once a second and applies it whenever
+    /// it changes, using the built-in transports.
+    ///
+    /// The file must hold a valid configuration when this is called. Each
+    /// version is compared to the last one applied, and only the keys whose
+    /// value changed are applied. Changes to `level`, `levels`, `format`,
+    /// `backpressure_strategy` and `transports` are applied by the worker
+    /// between entries, so nothing already queued is dropped: earlier
+    /// entries go to the old transports, which are flushed, and later ones
+    /// to the new. A changed `transports` list replaces every current
+    /// transport, including ones added in code. Keys removed from the file
+    /// keep their current value, and a [`DiagnosticKind::Config`] diagnostic
+    /// names them. `channel_capacity` is only read at startup; use
+    /// [`set_channel_capacity`](Self::set_channel_capacity) for that. If a
+    /// change can't be loaded, or names a level the logger doesn't have, the
+    /// current configuration is kept and a [`DiagnosticKind::Config`]
+    /// diagnostic is reported.
+    ///
+    /// Watching another file replaces the current watch.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let logger = LoggerBuilder::from_config_file("logging.toml")?.build();
+    /// logger.watch_config("logging.toml")?;
+    /// ```
+    #[cfg(feature = "config")]
+    pub fn watch_config(&self, path: impl Into<std::path::PathBuf>) -> Result<(), String> {
+        self.watch_config_with(path, ConfigLoader::new(), CONFIG_POLL_INTERVAL)
+    }
+
+    /// Like [`watch_config`](Self::watch_config), building transports with
+    /// `loader` and checking the file every `interval`.
+    #[cfg(feature = "config")]
+    pub fn watch_config_with(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+        loader: ConfigLoader,
+        interval: Duration,
+    ) -> Result<(), String> {
+        let watch = ConfigWatch::new(path.into(), loader, interval)?;
+        self.send(LogMessage::Watch(Some(Box::new(watch))))
+            .map_err(|_| "Logger is closed".to_string())
+    }
+
+    /// Stops watching the config file set up by [`watch_config`](Self::watch_config).
+    #[cfg(feature = "config")]
+    pub fn unwatch_config(&self) {
+        let _ = self.send(LogMessage::Watch(None));
+    }
+
+    /// Stores `strategy`, setting up the spill file it needs.
+    fn apply_backpressure_strategy(state: &mut SharedState, strategy: BackpressureStrategy) {
+        if let BackpressureStrategy::SpillToFile(path) = &strategy {
+            if state
+                .spool
+                .as_ref()
+                .is_none_or(|spool| spool.path() != path)
+            {
+                let spool = Arc::new(Spool::new(path));
+                if let Some(old) = state.spool.replace(Arc::clone(&spool)) {
+                    Self::move_spooled_entries(state, &old, &spool);
+                }
+            }
+        }
+        state.options.backpressure_strategy = Some(strategy);
+    }
+
+    fn move_spooled_entries(state: &SharedState, from: &Spool, to: &Spool) {
+        if !from.has_pending() {
+            return;
+        }
+        let moved = from
+            .take()
+            .and_then(|entries| entries.iter().try_for_each(|entry| to.write(entry)));
+        if let Err(e) = moved {
+            state.report(DiagnosticKind::Internal, || {
+                format!(
+                    "Failed to move spilled entries from {} to {}: {}",
+                    from.path().display(),
+                    to.path().display(),
+                    e
+                )
+            });
+        }
     }
 
     /// Start building a transport configuration. Use the builder to configure
@@ -539,7 +2122,7 @@ impl Logger {
     pub fn transport(
         &self,
         transport: impl Transport<LogInfo> + Send + Sync + 'static,
-    ) -> TransportBuilder {
+    ) -> TransportBuilder<'_> {
         TransportBuilder {
             logger: self,
             logger_transport: LoggerTransport::new(transport),
@@ -568,12 +2151,13 @@ impl Logger {
         let handle = TransportHandle::new();
         let logger_transport = transport.into_logger_transport();
 
-        let mut state = self.shared_state.write();
+        let mut state = self.inner.shared_state.write();
         if let Some(transports) = &mut state.options.transports {
             transports.push((handle, logger_transport));
         } else {
             state.options.transports = Some(vec![(handle, logger_transport)]);
         }
+        Self::refresh_effective_levels(&mut state);
 
         handle
     }
@@ -581,11 +2165,12 @@ impl Logger {
     /// Remove a transport by its handle.
     /// Returns `true` if the transport was found and removed, `false` otherwise.
     pub fn remove_transport(&self, handle: TransportHandle) -> bool {
-        let mut state = self.shared_state.write();
+        let mut state = self.inner.shared_state.write();
 
         if let Some(transports) = &mut state.options.transports {
             if let Some(index) = transports.iter().position(|(h, _)| *h == handle) {
                 transports.remove(index);
+                Self::refresh_effective_levels(&mut state);
                 return true;
             }
         }
@@ -595,7 +2180,9 @@ impl Logger {
 
 impl Drop for Logger {
     fn drop(&mut self) {
-        self.close();
+        if self.owner {
+            self.close();
+        }
     }
 }
 
@@ -611,11 +2198,20 @@ use log::{Log, Metadata, Record};
 #[cfg(feature = "log-backend")]
 impl Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        let state = self.shared_state.read();
-        Self::is_level_enabled(&metadata.level().as_str().to_lowercase(), &state)
+        let state = self.inner.shared_state.read();
+        Self::is_target_enabled(
+            &metadata.level().as_str().to_lowercase(),
+            Some(metadata.target()),
+            None,
+            &state,
+        )
     }
 
     fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
         // Convert log::Record to LogInfo
         let mut meta = std::collections::HashMap::new();
         // Add timestamp
@@ -759,7 +2355,7 @@ mod tests {
     #[test]
     fn test_logger_creation_with_default_options() {
         let logger = Logger::new(None);
-        assert!(logger.shared_state.read().options.levels.is_some());
+        assert!(logger.inner.shared_state.read().options.levels.is_some());
     }
 
     #[test]
@@ -767,7 +2363,7 @@ mod tests {
         let options = LoggerOptions::new().level("debug").channel_capacity(512);
 
         let logger = Logger::new(Some(options));
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.level.as_deref(), Some("debug"));
     }
 
@@ -779,7 +2375,7 @@ mod tests {
         let handle = logger.add_transport(transport);
 
         {
-            let state = logger.shared_state.read();
+            let state = logger.inner.shared_state.read();
             assert_eq!(state.options.transports.as_ref().unwrap().len(), 1);
         }
 
@@ -794,7 +2390,7 @@ mod tests {
         let handle1 = logger.add_transport(TestTransport::new());
         let handle2 = logger.add_transport(TestTransport::new());
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.transports.as_ref().unwrap().len(), 2);
 
         // Verify handles are different
@@ -808,7 +2404,7 @@ mod tests {
 
         assert!(logger.remove_transport(handle));
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert!(state.options.transports.as_ref().unwrap().is_empty());
     }
 
@@ -909,6 +2505,104 @@ mod tests {
         assert_eq!(logs[0].message, "Passes transport filter");
     }
 
+    #[test]
+    fn test_transport_only_level_does_not_duplicate() {
+        let logger = Logger::new(Some(
+            LoggerOptions::new()
+                .level("trace")
+                .format(logform::passthrough()),
+        ));
+        let errors = TestTransport::new();
+        let audit = TestTransport::new();
+        logger.add_transport(LoggerTransport::new(errors.clone()).with_only_level("error"));
+        logger.add_transport(LoggerTransport::new(audit.clone()).with_only_level("info"));
+
+        for level in ["error", "warn", "info", "debug"] {
+            logger.log(LogInfo::new(level, "Entry"));
+        }
+        logger.flush().unwrap();
+
+        let errors = errors.get_logs();
+        let audit = audit.get_logs();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].level, "error");
+        assert_eq!(audit.len(), 1);
+        assert_eq!(audit[0].level, "info");
+    }
+
+    #[test]
+    fn test_transport_level_range_and_set() {
+        let logger = Logger::new(Some(LoggerOptions::new().level("error")));
+        let range = TestTransport::new();
+        let set = TestTransport::new();
+        logger
+            .add_transport(LoggerTransport::new(range.clone()).with_level_range("debug"..="info"));
+        logger.add_transport(LoggerTransport::new(set.clone()).with_levels(["warn", "trace"]));
+
+        // The filters widen what the logger accepts
+        assert!(logger.is_enabled("trace"));
+
+        for level in ["error", "warn", "info", "debug", "trace"] {
+            logger.log(LogInfo::new(level, "Entry"));
+        }
+        logger.flush().unwrap();
+
+        let range_levels: Vec<_> = range.get_logs().into_iter().map(|l| l.level).collect();
+        let set_levels: Vec<_> = set.get_logs().into_iter().map(|l| l.level).collect();
+        assert_eq!(range_levels, ["info", "debug"]);
+        assert_eq!(set_levels, ["warn", "trace"]);
+    }
+
+    #[test]
+    fn test_transport_filter_routes_by_meta() {
+        let logger = Logger::new(Some(LoggerOptions::new().format(logform::passthrough())));
+        let acme = TestTransport::new();
+        let everything = TestTransport::new();
+        logger.add_transport(
+            LoggerTransport::new(acme.clone())
+                .with_filter(|info: &LogInfo| info.meta.get("tenant") == Some(&"acme".into())),
+        );
+        logger.add_transport(everything.clone());
+
+        logger.log(LogInfo::new("info", "For acme").with_meta("tenant", "acme"));
+        logger.log(LogInfo::new("info", "For globex").with_meta("tenant", "globex"));
+        logger.flush().unwrap();
+
+        let acme_logs = acme.get_logs();
+        assert_eq!(acme_logs.len(), 1);
+        assert_eq!(acme_logs[0].message, "For acme");
+        assert_eq!(everything.get_logs().len(), 2);
+    }
+
+    #[test]
+    fn test_logger_filter_can_be_reconfigured() {
+        let logger = Logger::new(Some(
+            LoggerOptions::new().filter(|info: &LogInfo| !info.message.contains("healthcheck")),
+        ));
+        let transport = TestTransport::new();
+        logger.add_transport(transport.clone());
+
+        logger.log(LogInfo::new("info", "GET /healthcheck"));
+        logger.log(LogInfo::new("info", "GET /users"));
+        logger.flush().unwrap();
+        assert_eq!(transport.get_logs().len(), 1);
+
+        logger.configure(Some(
+            LoggerOptions::new()
+                .format(logform::passthrough())
+                .filter(|info: &LogInfo| info.message.contains("healthcheck")),
+        ));
+        logger.add_transport(transport.clone());
+
+        logger.log(LogInfo::new("info", "GET /healthcheck"));
+        logger.log(LogInfo::new("info", "GET /users"));
+        logger.flush().unwrap();
+
+        let logs = transport.get_logs();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[1].message, "GET /healthcheck");
+    }
+
     #[test]
     fn test_empty_message_handling() {
         let logger = Logger::new(None);
@@ -947,16 +2641,58 @@ mod tests {
         let logger = Logger::new(None);
         logger.add_transport(TestTransport::new());
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.transports.as_ref().unwrap().len(), 1);
         drop(state);
 
         logger.configure(Some(LoggerOptions::new()));
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert!(state.options.transports.as_ref().unwrap().is_empty());
     }
 
+    #[test]
+    fn test_default_meta_merged_into_entries() {
+        let logger = Logger::new(Some(
+            LoggerOptions::new()
+                .format(logform::passthrough())
+                .default_meta([("service", "api"), ("region", "eu")]),
+        ));
+        let transport = TestTransport::new();
+        logger.add_transport(transport.clone());
+
+        logger.log(LogInfo::new("info", "Plain"));
+        logger.log(LogInfo::new("info", "Overridden").with_meta("region", "us"));
+        logger.flush().unwrap();
+
+        let logs = transport.get_logs();
+        assert_eq!(logs[0].meta["service"], "api");
+        assert_eq!(logs[0].meta["region"], "eu");
+        assert_eq!(logs[1].meta["region"], "us");
+    }
+
+    #[test]
+    fn test_configure_updates_default_meta() {
+        let logger = Logger::new(Some(
+            LoggerOptions::new()
+                .format(logform::passthrough())
+                .default_meta([("version", "1.0.0")]),
+        ));
+
+        logger.configure(Some(
+            LoggerOptions::new()
+                .format(logform::passthrough())
+                .default_meta([("version", "2.0.0")]),
+        ));
+        let transport = TestTransport::new();
+        logger.add_transport(transport.clone());
+
+        logger.log(LogInfo::new("info", "After reconfigure"));
+        logger.flush().unwrap();
+
+        assert_eq!(transport.get_logs()[0].meta["version"], "2.0.0");
+    }
+
     #[test]
     fn test_flush_returns_ok() {
         let logger = Logger::new(None);
@@ -994,8 +2730,38 @@ mod tests {
 
         logger.flush().unwrap();
 
-        let buffer = logger.buffer.lock().unwrap();
+        let buffer = logger.inner.buffer.lock().unwrap();
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_disabled_levels_never_reach_channel() {
+        let logger = Logger::new(Some(LoggerOptions::new().level("warn")));
+
+        logger.log(LogInfo::new("debug", "Filtered before queueing"));
+        logger.log(LogInfo::new("error", "Buffered"));
+        logger.flush().unwrap();
+
+        let buffer = logger.inner.buffer.lock().unwrap();
         assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer[0].message, "Buffered");
+    }
+
+    #[test]
+    fn test_is_enabled_tracks_transport_levels() {
+        let logger = Logger::new(Some(LoggerOptions::new().level("info")));
+        assert!(logger.is_enabled("info"));
+        assert!(!logger.is_enabled("debug"));
+        assert!(!logger.is_enabled("unknown"));
+
+        let handle = logger
+            .transport(TestTransport::new())
+            .with_level("trace")
+            .add();
+        assert!(logger.is_enabled("trace"));
+
+        logger.remove_transport(handle);
+        assert!(!logger.is_enabled("trace"));
     }
 
     #[test]
@@ -1006,7 +2772,7 @@ mod tests {
         logger.log(LogInfo::new("info", "Buffered"));
 
         logger.flush().unwrap();
-        let buffer = logger.buffer.lock().unwrap();
+        let buffer = logger.inner.buffer.lock().unwrap();
         assert_eq!(buffer.len(), 1);
         drop(buffer);
 
@@ -1083,7 +2849,7 @@ mod tests {
     fn test_transport_accepts_raw_transport() {
         let logger = Logger::builder().transport(TestTransport::new()).build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.transports.as_ref().unwrap().len(), 1);
     }
 
@@ -1118,7 +2884,7 @@ mod tests {
         let handle = logger.add_transport(transport.clone());
 
         {
-            let state = logger.shared_state.read();
+            let state = logger.inner.shared_state.read();
             assert_eq!(state.options.transports.as_ref().unwrap().len(), 1);
         }
 
@@ -1156,7 +2922,7 @@ mod tests {
             .transports(vec![TestTransport::new(), TestTransport::new()])
             .build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.transports.as_ref().unwrap().len(), 2);
     }
 }
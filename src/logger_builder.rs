@@ -1,27 +1,106 @@
 use crate::{
-    logger_options::{BackpressureStrategy, LoggerOptions},
+    diagnostics::{DiagnosticKind, Diagnostics},
+    directives::Directives,
+    logger::TransportHandle,
+    logger_levels::LoggerLevels,
+    logger_options::{BackpressureStrategy, BufferOverflowPolicy, LoggerOptions},
     logger_transport::IntoLoggerTransport,
+    rate_limit::RateLimiter,
+    sampling::Sampler,
     Logger,
 };
 use logform::{Format, LogInfo};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 pub struct LoggerBuilder {
     options: LoggerOptions,
+    // Reported on build, once the diagnostics sink is known
+    invalid_directives: Vec<String>,
 }
 
 impl LoggerBuilder {
     pub fn new() -> Self {
+        Self::from_options(LoggerOptions::default())
+    }
+
+    /// Starts from options loaded from a TOML or JSON file, which the other
+    /// builder methods can then adjust.
+    ///
+    /// See [`ConfigLoader`](crate::ConfigLoader) for the file format.
+    #[cfg(feature = "config")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        Ok(Self::from_options(LoggerOptions::from_config_file(path)?))
+    }
+
+    /// Starts from previously loaded options.
+    pub fn from_options(options: LoggerOptions) -> Self {
         LoggerBuilder {
-            options: LoggerOptions::default(),
+            options,
+            invalid_directives: Vec::new(),
         }
     }
 
+    /// Starts from the `RUST_LOG`-style level directives in the environment
+    /// variable `var`, such as `info,my_crate::db=debug,hyper=warn`.
+    ///
+    /// See [`level_directives`](Self::level_directives). If the variable is
+    /// not set, this is the same as [`new`](Self::new).
+    ///
+    /// # Example
+    /// ```ignore
+    /// // WINSTON_LOG="warn,my_app=debug"
+    /// let logger = LoggerBuilder::from_env("WINSTON_LOG")
+    ///     .transport(stdout())
+    ///     .build();
+    /// ```
+    pub fn from_env(var: &str) -> Self {
+        match std::env::var(var) {
+            Ok(spec) => Self::new().level_directives(&spec),
+            Err(_) => Self::new(),
+        }
+    }
+
+    /// Applies comma-separated level directives like those of `env_logger`.
+    ///
+    /// A bare level, such as `info`, sets the logger's [`level`](Self::level);
+    /// a bare `off` turns off every entry not matching a target.
+    /// `target=level` sets a [`target_level`](Self::target_level), which
+    /// applies to entries whose `target` or `module_path` meta is that module
+    /// or one of its submodules. A bare name that isn't one of the levels set
+    /// so far, such as `my_app`, enables every level for that target.
+    /// Directives that can't be parsed, or that set a target to a level that
+    /// isn't `off` or one of the levels set so far, are skipped and reported
+    /// to the [`diagnostics`](Self::diagnostics) sink when the logger is built.
+    pub fn level_directives(mut self, spec: &str) -> Self {
+        let default_levels;
+        let levels = match &self.options.levels {
+            Some(levels) => levels,
+            None => {
+                default_levels = LoggerLevels::default();
+                &default_levels
+            }
+        };
+        let directives = Directives::parse(spec, levels);
+        self.invalid_directives.extend(directives.invalid);
+        if let Some(level) = directives.level {
+            self = self.level(level);
+        }
+        for (target, level) in directives.targets {
+            self = self.target_level(target, level);
+        }
+        self
+    }
+
     pub fn level<T: Into<String>>(mut self, level: T) -> Self {
         self.options = self.options.level(level);
         self
     }
 
+    pub fn target_level(mut self, target: impl Into<String>, level: impl Into<String>) -> Self {
+        self.options = self.options.target_level(target, level);
+        self
+    }
+
     pub fn format<F>(mut self, format: F) -> Self
     where
         F: Format<Input = LogInfo> + Send + Sync + 'static,
@@ -44,6 +123,11 @@ impl LoggerBuilder {
         self
     }
 
+    pub fn exception_handler(mut self, transport: impl IntoLoggerTransport) -> Self {
+        self.options = self.options.exception_handler(transport);
+        self
+    }
+
     pub fn levels(mut self, levels: HashMap<String, u8>) -> Self {
         self.options = self.options.levels(levels);
         self
@@ -59,11 +143,90 @@ impl LoggerBuilder {
         self
     }
 
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.options = self.options.batch_size(size);
+        self
+    }
+
+    pub fn batch_latency(mut self, latency: Duration) -> Self {
+        self.options = self.options.batch_latency(latency);
+        self
+    }
+
+    pub fn priority_level<T: Into<String>>(mut self, level: T) -> Self {
+        self.options = self.options.priority_level(level);
+        self
+    }
+
+    pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+        self.options = self.options.buffer_capacity(capacity);
+        self
+    }
+
+    pub fn buffer_overflow(mut self, policy: BufferOverflowPolicy) -> Self {
+        self.options = self.options.buffer_overflow(policy);
+        self
+    }
+
+    pub fn default_meta<I, K, V>(mut self, meta: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<serde_json::Value>,
+    {
+        self.options = self.options.default_meta(meta);
+        self
+    }
+
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&LogInfo) -> bool + Send + Sync + 'static,
+    {
+        self.options = self.options.filter(filter);
+        self
+    }
+
+    pub fn rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.options = self.options.rate_limit(limiter);
+        self
+    }
+
+    pub fn sampling(mut self, sampler: Sampler) -> Self {
+        self.options = self.options.sampling(sampler);
+        self
+    }
+
+    pub fn on_error<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(TransportHandle, &str) + Send + Sync + 'static,
+    {
+        self.options = self.options.on_error(handler);
+        self
+    }
+
+    pub fn diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.options = self.options.diagnostics(diagnostics);
+        self
+    }
+
     pub fn build(self) -> Logger {
+        for invalid in self.invalid_directives {
+            Diagnostics::report_to(
+                self.options.diagnostics.as_ref(),
+                DiagnosticKind::Config,
+                || format!("Ignoring invalid level directive {:?}", invalid),
+            );
+        }
         Logger::new(Some(self.options))
     }
 }
 
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,7 +238,7 @@ mod tests {
         let builder = LoggerBuilder::new();
         let logger = builder.build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert!(state.options.levels.is_some());
         assert_eq!(state.options.level.as_deref(), Some("info"));
     }
@@ -84,7 +247,7 @@ mod tests {
     fn test_builder_with_level() {
         let logger = LoggerBuilder::new().level("debug").build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.level.as_deref(), Some("debug"));
     }
 
@@ -92,7 +255,7 @@ mod tests {
     fn test_builder_with_channel_capacity() {
         let logger = LoggerBuilder::new().channel_capacity(2048).build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.channel_capacity, Some(2048));
     }
 
@@ -102,7 +265,7 @@ mod tests {
             .backpressure_strategy(BackpressureStrategy::DropOldest)
             .build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert!(matches!(
             state.options.backpressure_strategy,
             Some(BackpressureStrategy::DropOldest)
@@ -117,12 +280,24 @@ mod tests {
 
         let logger = LoggerBuilder::new().levels(custom_levels.clone()).build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         let levels = state.options.levels.as_ref().unwrap();
         assert_eq!(levels.get_severity("critical"), Some(0));
         assert_eq!(levels.get_severity("normal"), Some(5));
     }
 
+    #[test]
+    fn test_builder_with_default_meta() {
+        let logger = LoggerBuilder::new()
+            .default_meta([("service", "api"), ("version", "1.2.0")])
+            .build();
+
+        let state = logger.inner.shared_state.read();
+        let default_meta = state.options.default_meta.as_ref().unwrap();
+        assert_eq!(default_meta["service"], "api");
+        assert_eq!(default_meta["version"], "1.2.0");
+    }
+
     #[test]
     fn test_builder_chaining() {
         let logger = LoggerBuilder::new()
@@ -131,7 +306,7 @@ mod tests {
             .backpressure_strategy(BackpressureStrategy::Block)
             .build();
 
-        let state = logger.shared_state.read();
+        let state = logger.inner.shared_state.read();
         assert_eq!(state.options.level.as_deref(), Some("warn"));
         assert_eq!(state.options.channel_capacity, Some(512));
         assert!(matches!(
@@ -17,12 +17,74 @@ impl LoggerLevels {
         self.levels.get(key).copied()
     }
 
+    /// Returns the level with the highest severity number, which lets every entry through.
+    pub(crate) fn most_verbose(&self) -> Option<&str> {
+        self.levels
+            .iter()
+            .max_by_key(|(_, severity)| **severity)
+            .map(|(level, _)| level.as_str())
+    }
+
     /*/// Returns an iterator over (level_name, severity) pairs.
     pub fn iter(&self) -> impl Iterator<Item = (&String, &u8)> {
         self.levels.iter()
     }*/
 }
 
+/// Selects which levels a transport accepts, beyond the plain threshold set
+/// with [`LoggerTransport::with_level`](crate::LoggerTransport::with_level).
+///
+/// Levels are resolved through the logger's [`LoggerLevels`], so ranges follow
+/// severity order rather than the order the bounds are written in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelFilter {
+    /// Accepts exactly one level.
+    Only(String),
+    /// Accepts every level whose severity lies between the two bounds, inclusive.
+    Range(String, String),
+    /// Accepts any of the listed levels.
+    AnyOf(Vec<String>),
+}
+
+impl LevelFilter {
+    /// Returns `true` if an entry at `level` passes this filter.
+    pub fn accepts(&self, level: &str, levels: &LoggerLevels) -> bool {
+        match self {
+            LevelFilter::Only(only) => only == level,
+            LevelFilter::AnyOf(allowed) => allowed.iter().any(|allowed| allowed == level),
+            LevelFilter::Range(start, end) => {
+                match (
+                    levels.get_severity(level),
+                    levels.get_severity(start),
+                    levels.get_severity(end),
+                ) {
+                    (Some(severity), Some(start), Some(end)) => {
+                        (start.min(end)..=start.max(end)).contains(&severity)
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// Returns the largest severity (the most verbose level) this filter can accept.
+    pub fn max_severity(&self, levels: &LoggerLevels) -> Option<u8> {
+        match self {
+            LevelFilter::Only(only) => levels.get_severity(only),
+            LevelFilter::AnyOf(allowed) => allowed
+                .iter()
+                .filter_map(|level| levels.get_severity(level))
+                .max(),
+            LevelFilter::Range(start, end) => {
+                match (levels.get_severity(start), levels.get_severity(end)) {
+                    (Some(start), Some(end)) => Some(start.max(end)),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
 impl IntoIterator for LoggerLevels {
     type Item = (String, u8);
     type IntoIter = std::collections::hash_map::IntoIter<String, u8>;
@@ -1,20 +1,39 @@
 use crate::{
+    diagnostics::Diagnostics,
     logger::TransportHandle,
     logger_levels::LoggerLevels,
-    logger_transport::{IntoLoggerTransport, LoggerTransport},
+    logger_transport::{IntoLoggerTransport, LogFilter, LoggerTransport},
+    rate_limit::RateLimiter,
+    sampling::Sampler,
 };
 use logform::{json, Format, LogInfo};
-use std::{collections::HashMap, sync::Arc};
-use winston_transport::Transport;
+use serde_json::Value;
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+/// A callback invoked with the handle and error of a transport that failed.
+pub type ErrorHandler = Arc<dyn Fn(TransportHandle, &str) + Send + Sync>;
 
 #[derive(Clone)]
 pub struct LoggerOptions {
     pub levels: Option<LoggerLevels>,
     pub format: Option<Arc<dyn Format<Input = LogInfo> + Send + Sync>>,
     pub level: Option<String>,
+    pub target_levels: Option<HashMap<String, String>>,
     pub transports: Option<Vec<(TransportHandle, LoggerTransport<LogInfo>)>>,
     pub channel_capacity: Option<usize>,
     pub backpressure_strategy: Option<BackpressureStrategy>,
+    pub priority_level: Option<String>,
+    pub batch_size: Option<usize>,
+    pub batch_latency: Option<Duration>,
+    pub buffer_capacity: Option<usize>,
+    pub buffer_overflow: Option<BufferOverflowPolicy>,
+    pub default_meta: Option<HashMap<String, Value>>,
+    pub exception_handlers: Option<Vec<LoggerTransport<LogInfo>>>,
+    pub filter: Option<LogFilter<LogInfo>>,
+    pub rate_limit: Option<RateLimiter>,
+    pub sampling: Option<Sampler>,
+    pub on_error: Option<ErrorHandler>,
+    pub diagnostics: Option<Diagnostics>,
 }
 
 impl LoggerOptions {
@@ -23,6 +42,15 @@ impl LoggerOptions {
         Self::default()
     }
 
+    /// Loads options from a TOML or JSON file, with the built-in transports.
+    ///
+    /// See [`ConfigLoader`](crate::ConfigLoader) for the file format and for
+    /// registering other transports.
+    #[cfg(feature = "config")]
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, String> {
+        crate::ConfigLoader::new().load_file(path)
+    }
+
     /// Sets the logging level for the logger.
     ///
     /// # Arguments
@@ -33,6 +61,26 @@ impl LoggerOptions {
         self
     }
 
+    /// Sets the level for entries from `target`, in place of the logger's
+    /// [`level`](Self::level).
+    ///
+    /// An entry's target is its `target` meta field, as recorded by the `log`
+    /// crate integration, or failing that its `module_path`. A target also
+    /// covers its submodules: `my_crate::db` applies to `my_crate::db::pool`,
+    /// and the most specific match wins. The level `off` drops every entry
+    /// from the target. Transports with a level of their own keep it.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - A module path such as `my_crate::db`.
+    /// * `level` - The level for entries from that target, or `off`.
+    pub fn target_level(mut self, target: impl Into<String>, level: impl Into<String>) -> Self {
+        self.target_levels
+            .get_or_insert_with(HashMap::new)
+            .insert(target.into(), level.into());
+        self
+    }
+
     /// Sets the log format for the logger.
     ///
     /// # Arguments
@@ -117,6 +165,21 @@ impl LoggerOptions {
         self
     }
 
+    /// Adds a transport that receives uncaught panics.
+    ///
+    /// When at least one exception handler is configured, [`Logger::new`](crate::Logger::new)
+    /// installs a panic hook that writes each panic as an `error` entry to these
+    /// transports and flushes them before the panic continues. See
+    /// [`Logger::handle_panics`](crate::Logger::handle_panics).
+    ///
+    /// This method is **additive**, like [`transport`](Self::transport).
+    pub fn exception_handler(mut self, transport: impl IntoLoggerTransport) -> Self {
+        self.exception_handlers
+            .get_or_insert_with(Vec::new)
+            .push(transport.into_logger_transport());
+        self
+    }
+
     /// Sets custom logging levels for the logger.
     ///
     /// # Arguments
@@ -137,6 +200,127 @@ impl LoggerOptions {
         self
     }
 
+    /// Sets how many entries are held while no transport is configured.
+    ///
+    /// Entries logged before the first transport is added wait in this buffer
+    /// and are written once one is. Defaults to 1024.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of buffered entries.
+    pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+        self.buffer_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets what happens when the no-transport buffer is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The overflow policy; defaults to [`BufferOverflowPolicy::DropOldest`].
+    pub fn buffer_overflow(mut self, policy: BufferOverflowPolicy) -> Self {
+        self.buffer_overflow = Some(policy);
+        self
+    }
+
+    /// Sets metadata merged into every entry before it is formatted.
+    ///
+    /// Useful for fields that describe the process rather than the event, such as
+    /// the service name, hostname, pid or version. When an entry carries its own
+    /// value for one of these keys, the entry's value is kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `meta` - Key-value pairs to add to every entry.
+    pub fn default_meta<I, K, V>(mut self, meta: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<Value>,
+    {
+        self.default_meta = Some(
+            meta.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets a predicate that every entry must satisfy before reaching any transport.
+    ///
+    /// The filter runs in the worker after default metadata is merged and
+    /// before formatting. Transports can add their own filters with
+    /// [`LoggerTransport::with_filter`].
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Returns `true` for entries that should be written.
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&LogInfo) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Collapses repeated entries and caps entry rates before any transport
+    /// sees them.
+    ///
+    /// The limiter runs in the worker after [`filter`](Self::filter). Transports
+    /// can add their own with [`LoggerTransport::with_rate_limit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `limiter` - The repeat window and token-bucket limits to apply.
+    pub fn rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limit = Some(limiter);
+        self
+    }
+
+    /// Keeps only a sample of high-volume levels.
+    ///
+    /// Sampling happens in the calling thread before entries enter the
+    /// channel, so entries sampled out never reach the worker. Transports can
+    /// sample further with [`LoggerTransport::with_sampling`].
+    ///
+    /// # Arguments
+    ///
+    /// * `sampler` - The per-level sampling rules.
+    pub fn sampling(mut self, sampler: Sampler) -> Self {
+        self.sampling = Some(sampler);
+        self
+    }
+
+    /// Sets a callback that is told about transport failures.
+    ///
+    /// The callback runs on the worker thread each time a transport's `flush`
+    /// fails, with the failing transport's handle and its error. The same
+    /// failures are also returned, aggregated, from [`Logger::flush`](crate::Logger::flush).
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Receives the transport handle and the error message.
+    pub fn on_error<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(TransportHandle, &str) + Send + Sync + 'static,
+    {
+        self.on_error = Some(Arc::new(handler));
+        self
+    }
+
+    /// Sets where the logger reports its own problems, such as dropped entries,
+    /// entries buffered without a transport, or failed flushes during close.
+    ///
+    /// Defaults to stderr, at most 10 diagnostics of each kind every 10 seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagnostics` - The sink, e.g. [`Diagnostics::silent`] or a rate-limited [`Diagnostics::stderr`].
+    pub fn diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+
     /// Sets the backpressure strategy for the logger.
     ///
     /// # Arguments
@@ -146,6 +330,83 @@ impl LoggerOptions {
         self.backpressure_strategy = Some(strategy);
         self
     }
+
+    /// Lets the worker hand up to `size` entries to each transport at once.
+    ///
+    /// The worker takes whatever is already queued, up to `size` entries, and
+    /// passes them to [`Transport::log_batch`](winston_transport::Transport::log_batch).
+    /// Transports that override `log_batch` can then write them with one
+    /// syscall or request; the rest fall back to one `log` call per entry.
+    /// Without this option, or with a size of 1, entries are delivered one at
+    /// a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The most entries delivered in one batch.
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = Some(size);
+        self
+    }
+
+    /// Sets how long the worker waits for a batch to fill before delivering it.
+    ///
+    /// Defaults to zero, so only entries that are already queued are batched.
+    /// Has no effect unless [`batch_size`](Self::batch_size) is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `latency` - The longest an entry is held back waiting for others.
+    pub fn batch_latency(mut self, latency: Duration) -> Self {
+        self.batch_latency = Some(latency);
+        self
+    }
+
+    /// Sets the least severe level that is never dropped by backpressure.
+    ///
+    /// Entries at or above this severity go through a separate priority lane
+    /// that the worker drains first, so an `error` still gets through while
+    /// `DropOldest` or `DropCurrent` are shedding lower-severity traffic. When
+    /// the priority lane itself is full, the caller blocks until it has room.
+    ///
+    /// Priority entries may be written ahead of lower-severity entries that
+    /// were still waiting in the channel. Each lane keeps its own order, and
+    /// a flush or close waits for priority entries logged before it.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - A level name from the logger's levels, e.g. `"error"`.
+    pub fn priority_level<T: Into<String>>(mut self, level: T) -> Self {
+        self.priority_level = Some(level.into());
+        self
+    }
+}
+
+impl LoggerOptions {
+    /// Options with nothing set, so that applying them changes nothing.
+    #[cfg(feature = "config")]
+    pub(crate) fn unset() -> Self {
+        LoggerOptions {
+            levels: None,
+            level: None,
+            target_levels: None,
+            transports: None,
+            format: None,
+            channel_capacity: None,
+            backpressure_strategy: None,
+            priority_level: None,
+            batch_size: None,
+            batch_latency: None,
+            buffer_capacity: None,
+            buffer_overflow: None,
+            default_meta: None,
+            exception_handlers: None,
+            filter: None,
+            rate_limit: None,
+            sampling: None,
+            on_error: None,
+            diagnostics: None,
+        }
+    }
 }
 
 impl Default for LoggerOptions {
@@ -158,15 +419,28 @@ impl Default for LoggerOptions {
     /// - The JSON format for log entries.
     /// - A channel capacity of 1024.
     /// - A backpressure strategy set to `BackpressureStrategy::Block`, meaning the logger will block on overflow until space is available.
-
+    /// - A no-transport buffer of 1024 entries that drops the oldest entry on overflow.
     fn default() -> Self {
         LoggerOptions {
             levels: Some(LoggerLevels::default()),
             level: Some("info".to_string()),
+            target_levels: None,
             transports: Some(Vec::new()),
             format: Some(Arc::new(json())),
             channel_capacity: Some(1024),
             backpressure_strategy: Some(BackpressureStrategy::Block),
+            priority_level: None,
+            batch_size: None,
+            batch_latency: None,
+            buffer_capacity: Some(1024),
+            buffer_overflow: Some(BufferOverflowPolicy::DropOldest),
+            default_meta: None,
+            exception_handlers: None,
+            filter: None,
+            rate_limit: None,
+            sampling: None,
+            on_error: None,
+            diagnostics: None,
         }
     }
 }
@@ -176,9 +450,25 @@ impl std::fmt::Debug for LoggerOptions {
         f.debug_struct("LoggerOptions")
             .field("levels", &self.levels)
             .field("level", &self.level)
+            .field("target_levels", &self.target_levels)
             .field("transports", &self.transports)
             .field("channel_capacity", &self.channel_capacity)
             .field("backpressure_strategy", &self.backpressure_strategy)
+            .field("priority_level", &self.priority_level)
+            .field("batch_size", &self.batch_size)
+            .field("batch_latency", &self.batch_latency)
+            .field("buffer_capacity", &self.buffer_capacity)
+            .field("buffer_overflow", &self.buffer_overflow)
+            .field("default_meta", &self.default_meta)
+            .field("exception_handlers", &self.exception_handlers)
+            .field("filter", &self.filter.as_ref().map(|_| "<filter fn>"))
+            .field("rate_limit", &self.rate_limit)
+            .field("sampling", &self.sampling)
+            .field(
+                "on_error",
+                &self.on_error.as_ref().map(|_| "<error handler fn>"),
+            )
+            .field("diagnostics", &self.diagnostics)
             // For the format field, just print a placeholder because it can't be debugged:
             .field("format", &"<Format trait object>")
             .finish()
@@ -190,4 +480,28 @@ pub enum BackpressureStrategy {
     DropOldest,
     Block,
     DropCurrent,
+    /// Block for up to the given time, then drop the entry.
+    BlockTimeout(Duration),
+    /// While the channel is full, keep 1 in every `n` entries (blocking for
+    /// room) and drop the rest.
+    Sample(u32),
+    /// Append overflowing entries to a local spool file, which the worker
+    /// replays once the channel drains. Replayed entries are written after the
+    /// backlog that was queued ahead of them.
+    ///
+    /// A [dedicated worker](crate::LoggerTransport::with_dedicated_worker)'s
+    /// queue has no spill file, so there this strategy behaves like `Block`.
+    SpillToFile(PathBuf),
+}
+
+/// What to do with entries logged while no transport is configured and the
+/// buffer holding them is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferOverflowPolicy {
+    /// Discard the oldest buffered entry to make room.
+    DropOldest,
+    /// Discard the entry being logged.
+    DropNewest,
+    /// Write the oldest buffered entry to stderr, formatted, to make room.
+    SpillToStderr,
 }
@@ -1,13 +1,30 @@
-use std::{fmt, sync::Arc};
+use std::{fmt, ops::RangeInclusive, sync::Arc};
 
+use crate::{
+    logger_levels::LevelFilter,
+    rate_limit::RateLimiter,
+    sampling::Sampler,
+    stats::StatsCounters,
+    transports::{QueueReporting, QueuedTransport},
+    BackpressureStrategy, Diagnostics,
+};
 use logform::{Format, LogInfo};
 use winston_transport::Transport;
 
+/// A predicate deciding whether an entry should be written.
+pub type LogFilter<L> = Arc<dyn Fn(&L) -> bool + Send + Sync>;
+
 #[derive(Clone)]
 pub struct LoggerTransport<L> {
     transport: Arc<dyn Transport<L> + Send + Sync>,
     level: Option<String>,
+    level_filter: Option<LevelFilter>,
     format: Option<Arc<dyn Format<Input = L> + Send + Sync>>,
+    filters: Vec<LogFilter<L>>,
+    rate_limit: Option<RateLimiter>,
+    sampling: Option<Sampler>,
+    // Set when delivery runs on a dedicated worker
+    queue_reporting: Option<Arc<QueueReporting>>,
 }
 
 impl<L> LoggerTransport<L> {
@@ -18,7 +35,12 @@ impl<L> LoggerTransport<L> {
         Self {
             transport: Arc::new(transport),
             level: None,
+            level_filter: None,
             format: None,
+            filters: Vec::new(),
+            rate_limit: None,
+            sampling: None,
+            queue_reporting: None,
         }
     }
 
@@ -27,6 +49,73 @@ impl<L> LoggerTransport<L> {
         self
     }
 
+    /// Restricts this transport to the levels selected by `filter`.
+    ///
+    /// A level filter takes precedence over the threshold set with
+    /// [`with_level`](Self::with_level).
+    pub fn with_level_filter(mut self, filter: LevelFilter) -> Self {
+        self.level_filter = Some(filter);
+        self
+    }
+
+    /// Accepts entries at exactly `level` and nothing else.
+    pub fn with_only_level(self, level: impl Into<String>) -> Self {
+        self.with_level_filter(LevelFilter::Only(level.into()))
+    }
+
+    /// Accepts entries whose level lies within `range`, e.g. `"info"..="debug"`.
+    pub fn with_level_range<S: Into<String>>(self, range: RangeInclusive<S>) -> Self {
+        let (start, end) = range.into_inner();
+        self.with_level_filter(LevelFilter::Range(start.into(), end.into()))
+    }
+
+    /// Accepts entries at any of the given levels.
+    pub fn with_levels<I, S>(self, levels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_level_filter(LevelFilter::AnyOf(
+            levels.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Adds a predicate that entries must satisfy to reach this transport.
+    ///
+    /// Filters run in the worker after level filtering and before formatting.
+    /// When several filters are added, an entry must pass all of them.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let acme = LoggerTransport::new(FileTransport::new("acme.log"))
+    ///     .with_filter(|info: &LogInfo| info.meta.get("tenant") == Some(&"acme".into()));
+    /// ```
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&L) -> bool + Send + Sync + 'static,
+    {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Collapses repeated entries and caps entry rates for this transport only.
+    ///
+    /// The limiter sees the entries that pass this transport's level and
+    /// filters. See [`RateLimiter`].
+    pub fn with_rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limit = Some(limiter);
+        self
+    }
+
+    /// Keeps only a sample of high-volume levels for this transport.
+    ///
+    /// Unlike logger-wide [sampling](crate::LoggerOptions::sampling), this
+    /// runs in the worker, after level filtering and filters. See [`Sampler`].
+    pub fn with_sampling(mut self, sampler: Sampler) -> Self {
+        self.sampling = Some(sampler);
+        self
+    }
+
     pub fn with_format<F>(mut self, format: F) -> Self
     where
         F: Format<Input = L> + Send + Sync + 'static,
@@ -39,6 +128,23 @@ impl<L> LoggerTransport<L> {
         self.level.as_ref()
     }
 
+    pub fn get_level_filter(&self) -> Option<&LevelFilter> {
+        self.level_filter.as_ref()
+    }
+
+    /// Returns `true` if `info` passes every filter added with [`with_filter`](Self::with_filter).
+    pub fn accepts(&self, info: &L) -> bool {
+        self.filters.iter().all(|filter| filter(info))
+    }
+
+    pub fn get_rate_limit(&self) -> Option<&RateLimiter> {
+        self.rate_limit.as_ref()
+    }
+
+    pub fn get_sampling(&self) -> Option<&Sampler> {
+        self.sampling.as_ref()
+    }
+
     pub fn get_format(&self) -> Option<Arc<dyn Format<Input = L> + Send + Sync>> {
         self.format.clone()
     }
@@ -46,6 +152,49 @@ impl<L> LoggerTransport<L> {
     pub fn get_transport(&self) -> &Arc<dyn Transport<L> + Send + Sync> {
         &self.transport
     }
+
+    /// Points a dedicated worker's drop reports at the logger this transport belongs to.
+    pub(crate) fn attach_reporting(
+        &self,
+        diagnostics: Option<&Diagnostics>,
+        stats: &Arc<StatsCounters>,
+    ) {
+        if let Some(reporting) = &self.queue_reporting {
+            reporting.attach(diagnostics, stats);
+        }
+    }
+}
+
+impl<L: Send + 'static> LoggerTransport<L> {
+    /// Moves delivery to this transport onto its own thread and bounded queue.
+    ///
+    /// Entries that reach a full queue are handled by `strategy`, independently
+    /// of the logger's channel. With [`DropCurrent`](BackpressureStrategy::DropCurrent)
+    /// or [`DropOldest`](BackpressureStrategy::DropOldest) a slow transport only
+    /// ever holds up itself. The other strategies wait for room in a full queue,
+    /// and while they wait the logger's worker can't deliver to any transport.
+    /// The queue has no spill file, so [`SpillToFile`](BackpressureStrategy::SpillToFile)
+    /// behaves like [`Block`](BackpressureStrategy::Block) here.
+    ///
+    /// Entries the queue drops are reported to the logger's diagnostics sink
+    /// and counted in [`Logger::stats`](crate::Logger::stats). Flushing or
+    /// closing the logger still waits for this queue to drain. See
+    /// [`QueuedTransport`].
+    pub fn with_dedicated_worker(
+        mut self,
+        capacity: usize,
+        strategy: BackpressureStrategy,
+    ) -> Self {
+        let reporting = Arc::new(QueueReporting::default());
+        self.transport = Arc::new(QueuedTransport::from_arc(
+            self.transport,
+            capacity,
+            strategy,
+            Arc::clone(&reporting),
+        ));
+        self.queue_reporting = Some(reporting);
+        self
+    }
 }
 
 impl<L> fmt::Debug for LoggerTransport<L> {
@@ -56,7 +205,11 @@ impl<L> fmt::Debug for LoggerTransport<L> {
                 &format!("Transport<{}>", std::any::type_name::<L>()),
             )
             .field("level", &self.level)
+            .field("level_filter", &self.level_filter)
             .field("format", &self.format.as_ref().map(|_| "Format<...>"))
+            .field("filters", &self.filters.len())
+            .field("rate_limit", &self.rate_limit)
+            .field("sampling", &self.sampling)
             .finish()
     }
 }
@@ -0,0 +1,92 @@
+use crate::ChildLogger;
+use logform::LogInfo;
+use std::time::{Duration, Instant};
+
+/// A running timer created by [`Logger::start_timer`],
+/// [`ChildLogger::start_timer`] or [`start_timer`](crate::start_timer) for the
+/// global logger.
+///
+/// The timer owns a handle to the logger, so it can be stored or moved into
+/// another thread. When finished with [`done`](Self::done) or [`done_with`](Self::done_with),
+/// or when dropped, the timer logs an entry carrying the elapsed time in a
+/// `duration_ms` meta field.
+///
+/// # Example
+/// ```rust
+/// use winston::Logger;
+///
+/// let logger = Logger::new(None);
+///
+/// let timer = logger.start_timer().with_level("debug");
+/// // ... slow operation ...
+/// timer.done("Fetched user profile");
+/// ```
+#[derive(Debug)]
+pub struct Profiler {
+    // A child without metadata when started from a `Logger`
+    logger: ChildLogger,
+    start: Instant,
+    level: String,
+    message: String,
+    finished: bool,
+}
+
+impl Profiler {
+    pub(crate) fn new(logger: ChildLogger) -> Self {
+        Profiler {
+            logger,
+            start: Instant::now(),
+            level: "info".to_string(),
+            message: "Timer completed".to_string(),
+            finished: false,
+        }
+    }
+
+    /// Sets the level of the entry logged when the timer finishes. Defaults to `info`.
+    pub fn with_level(mut self, level: impl Into<String>) -> Self {
+        self.level = level.into();
+        self
+    }
+
+    /// Sets the message logged if the timer is dropped without calling `done`.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Returns the time elapsed since the timer started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Stops the timer and logs `message` at the timer's level.
+    pub fn done(mut self, message: impl Into<String>) {
+        let entry = LogInfo::new(self.level.clone(), message);
+        self.finish(entry);
+    }
+
+    /// Stops the timer and logs `entry`, adding the `duration_ms` meta field.
+    ///
+    /// The entry's own level is used instead of the timer's level.
+    pub fn done_with(mut self, entry: LogInfo) {
+        self.finish(entry);
+    }
+
+    fn finish(&mut self, entry: LogInfo) {
+        self.finished = true;
+        self.logger.log(with_duration(entry, self.start.elapsed()));
+    }
+}
+
+impl Drop for Profiler {
+    fn drop(&mut self) {
+        if !self.finished {
+            let entry = LogInfo::new(self.level.clone(), self.message.clone());
+            self.finish(entry);
+        }
+    }
+}
+
+pub(crate) fn with_duration(entry: LogInfo, elapsed: Duration) -> LogInfo {
+    entry.with_meta("duration_ms", elapsed.as_millis() as u64)
+}
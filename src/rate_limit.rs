@@ -0,0 +1,401 @@
+use logform::LogInfo;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// How often idle token buckets are pruned when repeats are not being suppressed
+const DEFAULT_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Collapses repeated entries and caps how many entries get through, either
+/// for a whole logger ([`LoggerOptions::rate_limit`](crate::LoggerOptions::rate_limit))
+/// or for one transport ([`LoggerTransport::with_rate_limit`](crate::LoggerTransport::with_rate_limit)).
+///
+/// With [`suppress_repeats`](Self::suppress_repeats), the first entry with a
+/// given level and message is written and identical ones within the window
+/// are held back. When the window closes, the logger's worker writes one copy
+/// with a `repeated` meta field counting the entries it stands for, even if
+/// nothing else is logged in the meantime. Flushing the logger writes it
+/// straight away.
+///
+/// Token-bucket limits drop entries beyond a rate, per level or per value of
+/// a meta key. Clones share their state.
+///
+/// # Example
+/// ```ignore
+/// let limiter = RateLimiter::new()
+///     .suppress_repeats(Duration::from_secs(10))
+///     .limit_level("debug", 100, Duration::from_secs(1))
+///     .limit_key("tenant", 50, Duration::from_secs(1));
+/// ```
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    repeat_window: Option<Duration>,
+    level_limits: HashMap<String, Rate>,
+    key_limits: Vec<(String, Rate)>,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+/// The outcome of [`RateLimiter::check`].
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Admit,
+    /// Held back as a repeat of an entry already written in this window
+    Repeated,
+    /// Dropped because a token bucket was empty
+    Limited,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Rate {
+    max: u32,
+    per: Duration,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(rate: Rate, now: Instant) -> Self {
+        TokenBucket {
+            tokens: f64::from(rate.max),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, rate: Rate, now: Instant) {
+        let max = f64::from(rate.max);
+        if rate.per.is_zero() {
+            self.tokens = max;
+        } else {
+            let elapsed = now.duration_since(self.updated).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * max / rate.per.as_secs_f64()).min(max);
+        }
+        self.updated = now;
+    }
+}
+
+struct Repeat {
+    started: Instant,
+    suppressed: u64,
+    // The first entry held back, written with the count once the window closes
+    held: Option<LogInfo>,
+}
+
+impl Repeat {
+    fn take_summary(&mut self) -> Option<LogInfo> {
+        let mut summary = self.held.take()?;
+        summary
+            .meta
+            .insert("repeated".to_string(), Value::from(self.suppressed));
+        self.suppressed = 0;
+        Some(summary)
+    }
+}
+
+#[derive(Default)]
+struct LimiterState {
+    repeats: HashMap<(String, String), Repeat>,
+    level_buckets: HashMap<String, TokenBucket>,
+    key_buckets: HashMap<(String, String), TokenBucket>,
+    // Summaries of closed windows, waiting to be written
+    ready: Vec<LogInfo>,
+    last_sweep: Option<Instant>,
+    // When the earliest window holding back a repeat closes
+    next_close: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter that lets everything through until limits are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Holds back entries whose level and message match one already written
+    /// within `window`.
+    pub fn suppress_repeats(mut self, window: Duration) -> Self {
+        self.repeat_window = Some(window);
+        self
+    }
+
+    /// Lets through at most `max` entries at `level` per `per`, in bursts of up to `max`.
+    pub fn limit_level(mut self, level: impl Into<String>, max: u32, per: Duration) -> Self {
+        self.level_limits.insert(level.into(), Rate { max, per });
+        self
+    }
+
+    /// Lets through at most `max` entries per `per` for each distinct value of
+    /// the meta field `key`. Entries without the field are not limited by it.
+    pub fn limit_key(mut self, key: impl Into<String>, max: u32, per: Duration) -> Self {
+        self.key_limits.push((key.into(), Rate { max, per }));
+        self
+    }
+
+    /// Decides whether `entry` should be written.
+    pub(crate) fn check(&self, entry: &LogInfo) -> Verdict {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(window) = self.repeat_window {
+            let key = (entry.level.clone(), entry.message.clone());
+            let LimiterState {
+                repeats,
+                ready,
+                next_close,
+                ..
+            } = &mut *state;
+            match repeats.get_mut(&key) {
+                Some(repeat) if now.duration_since(repeat.started) < window => {
+                    repeat.suppressed += 1;
+                    if repeat.held.is_none() {
+                        repeat.held = Some(entry.clone());
+                        let closes = repeat.started + window;
+                        *next_close = Some(next_close.map_or(closes, |next| next.min(closes)));
+                    }
+                    return Verdict::Repeated;
+                }
+                Some(repeat) => {
+                    ready.extend(repeat.take_summary());
+                    repeat.started = now;
+                }
+                None => {
+                    repeats.insert(
+                        key,
+                        Repeat {
+                            started: now,
+                            suppressed: 0,
+                            held: None,
+                        },
+                    );
+                }
+            }
+        }
+
+        if self.take_tokens(&mut state, entry, now) {
+            Verdict::Admit
+        } else {
+            Verdict::Limited
+        }
+    }
+
+    /// Returns summaries of repeats whose window has closed, or of every
+    /// pending repeat when `flush` is set.
+    pub(crate) fn take_summaries(&self, flush: bool) -> Vec<LogInfo> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        let interval = self.repeat_window.unwrap_or(DEFAULT_SWEEP_INTERVAL);
+        let due = flush
+            || state.next_close.is_some_and(|next| next <= now)
+            || state
+                .last_sweep
+                .is_none_or(|last| now.duration_since(last) >= interval);
+        if due {
+            state.last_sweep = Some(now);
+            self.sweep(&mut state, now, flush);
+        }
+
+        std::mem::take(&mut state.ready)
+    }
+
+    /// Returns how long until the earliest window holding back a repeat
+    /// closes, so the worker can wake up and write its summary.
+    pub(crate) fn until_next_summary(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        state
+            .next_close
+            .map(|next| next.saturating_duration_since(Instant::now()))
+    }
+
+    fn sweep(&self, state: &mut LimiterState, now: Instant, flush: bool) {
+        let LimiterState {
+            repeats,
+            level_buckets,
+            key_buckets,
+            ready,
+            next_close,
+            ..
+        } = state;
+
+        *next_close = None;
+        if let Some(window) = self.repeat_window {
+            repeats.retain(|_, repeat| {
+                let closed = now.duration_since(repeat.started) >= window;
+                if closed || flush {
+                    ready.extend(repeat.take_summary());
+                }
+                if !closed && repeat.held.is_some() {
+                    let closes = repeat.started + window;
+                    *next_close = Some(next_close.map_or(closes, |next| next.min(closes)));
+                }
+                !closed
+            });
+        }
+
+        // A bucket that has refilled completely is the same as a new one
+        level_buckets.retain(|level, bucket| {
+            self.level_limits.get(level).is_some_and(|&rate| {
+                bucket.refill(rate, now);
+                bucket.tokens < f64::from(rate.max)
+            })
+        });
+        key_buckets.retain(|(key, _), bucket| {
+            self.key_rate(key).is_some_and(|rate| {
+                bucket.refill(rate, now);
+                bucket.tokens < f64::from(rate.max)
+            })
+        });
+    }
+
+    fn key_rate(&self, key: &str) -> Option<Rate> {
+        self.key_limits
+            .iter()
+            .find(|(limited, _)| limited == key)
+            .map(|(_, rate)| *rate)
+    }
+
+    /// Takes a token from every bucket that applies to `entry`, or from none
+    /// if any of them is empty.
+    fn take_tokens(&self, state: &mut LimiterState, entry: &LogInfo, now: Instant) -> bool {
+        let level = self.level_limits.get(&entry.level).copied();
+        let keys: Vec<_> = self
+            .key_limits
+            .iter()
+            .filter_map(|(key, rate)| {
+                let value = entry.meta.get(key)?;
+                Some(((key.clone(), value_key(value)), *rate))
+            })
+            .collect();
+
+        let mut available = true;
+        if let Some(rate) = level {
+            let bucket = state
+                .level_buckets
+                .entry(entry.level.clone())
+                .or_insert_with(|| TokenBucket::full(rate, now));
+            bucket.refill(rate, now);
+            available &= bucket.tokens >= 1.0;
+        }
+        for (key, rate) in &keys {
+            let bucket = state
+                .key_buckets
+                .entry(key.clone())
+                .or_insert_with(|| TokenBucket::full(*rate, now));
+            bucket.refill(*rate, now);
+            available &= bucket.tokens >= 1.0;
+        }
+        if !available {
+            return false;
+        }
+
+        if let Some(bucket) = state.level_buckets.get_mut(&entry.level) {
+            bucket.tokens -= 1.0;
+        }
+        for (key, _) in &keys {
+            if let Some(bucket) = state.key_buckets.get_mut(key) {
+                bucket.tokens -= 1.0;
+            }
+        }
+        true
+    }
+}
+
+fn value_key(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("repeat_window", &self.repeat_window)
+            .field("level_limits", &self.level_limits)
+            .field("key_limits", &self.key_limits)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeats_within_window_are_held_back() {
+        let limiter = RateLimiter::new().suppress_repeats(Duration::from_secs(60));
+        let entry = LogInfo::new("error", "Connection refused");
+
+        assert_eq!(limiter.check(&entry), Verdict::Admit);
+        for _ in 0..3 {
+            assert_eq!(limiter.check(&entry), Verdict::Repeated);
+        }
+        assert_eq!(
+            limiter.check(&LogInfo::new("warn", "Connection refused")),
+            Verdict::Admit
+        );
+
+        assert!(limiter.take_summaries(false).is_empty());
+        let summaries = limiter.take_summaries(true);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].meta.get("repeated"), Some(&Value::from(3)));
+    }
+
+    #[test]
+    fn test_closed_window_yields_summary() {
+        let limiter = RateLimiter::new().suppress_repeats(Duration::from_millis(20));
+        let entry = LogInfo::new("error", "Connection refused");
+
+        limiter.check(&entry);
+        limiter.check(&entry);
+        std::thread::sleep(Duration::from_millis(30));
+
+        // The next copy opens a new window and is written as usual
+        assert_eq!(limiter.check(&entry), Verdict::Admit);
+        let summaries = limiter.take_summaries(false);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].meta.get("repeated"), Some(&Value::from(1)));
+    }
+
+    #[test]
+    fn test_level_bucket() {
+        let limiter = RateLimiter::new().limit_level("debug", 2, Duration::from_secs(60));
+
+        let debug = LogInfo::new("debug", "Tick");
+        assert_eq!(limiter.check(&debug), Verdict::Admit);
+        assert_eq!(limiter.check(&debug), Verdict::Admit);
+        assert_eq!(limiter.check(&debug), Verdict::Limited);
+        assert_eq!(limiter.check(&LogInfo::new("info", "Tick")), Verdict::Admit);
+    }
+
+    #[test]
+    fn test_key_buckets_are_per_value() {
+        let limiter = RateLimiter::new().limit_key("tenant", 1, Duration::from_secs(60));
+
+        let acme = LogInfo::new("info", "Request").with_meta("tenant", "acme");
+        let globex = LogInfo::new("info", "Request").with_meta("tenant", "globex");
+        assert_eq!(limiter.check(&acme), Verdict::Admit);
+        assert_eq!(limiter.check(&acme), Verdict::Limited);
+        assert_eq!(limiter.check(&globex), Verdict::Admit);
+        assert_eq!(
+            limiter.check(&LogInfo::new("info", "Request")),
+            Verdict::Admit
+        );
+    }
+
+    #[test]
+    fn test_buckets_refill() {
+        let limiter = RateLimiter::new().limit_level("info", 1, Duration::from_millis(20));
+        let entry = LogInfo::new("info", "Tick");
+
+        assert_eq!(limiter.check(&entry), Verdict::Admit);
+        assert_eq!(limiter.check(&entry), Verdict::Limited);
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(limiter.check(&entry), Verdict::Admit);
+    }
+}
@@ -0,0 +1,243 @@
+use logform::LogInfo;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// The window for `Sampler::keep_first_then_every`
+const BURST_WINDOW: Duration = Duration::from_secs(1);
+
+/// Keeps a fraction of high-volume levels such as `debug` or `trace`, either
+/// for a whole logger ([`LoggerOptions::sampling`](crate::LoggerOptions::sampling))
+/// or for one transport ([`LoggerTransport::with_sampling`](crate::LoggerTransport::with_sampling)).
+///
+/// Levels without a rule are always kept. Entries kept at a rate below 1 get a
+/// `sample_rate` meta field with that rate, so counts can be re-weighted
+/// downstream. Sampling is deterministic: a 1% rule keeps the first entry and
+/// every hundredth one after it. Clones share their counters.
+///
+/// # Example
+/// ```ignore
+/// let sampler = Sampler::new()
+///     .keep_ratio("debug", 0.01)
+///     .keep_first_then_every("trace", 100, 50)
+///     .always_keep_key("trace_id");
+/// ```
+#[derive(Clone, Default)]
+pub struct Sampler {
+    rules: HashMap<String, Arc<Rule>>,
+    keep_keys: Vec<String>,
+}
+
+/// The outcome of [`Sampler::sample`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum Sample {
+    Keep,
+    /// Kept as one of a sample taken at this rate
+    KeepAt(f64),
+    Drop,
+}
+
+#[derive(Debug)]
+enum RuleKind {
+    Ratio(f64),
+    FirstThenEvery { first: u32, every: u32 },
+}
+
+#[derive(Debug)]
+struct Rule {
+    kind: RuleKind,
+    state: Mutex<RuleState>,
+}
+
+#[derive(Debug)]
+struct RuleState {
+    seen: u64,
+    window_started: Instant,
+    seen_in_window: u64,
+}
+
+impl Rule {
+    fn new(kind: RuleKind) -> Arc<Self> {
+        Arc::new(Rule {
+            kind,
+            state: Mutex::new(RuleState {
+                seen: 0,
+                window_started: Instant::now(),
+                seen_in_window: 0,
+            }),
+        })
+    }
+
+    fn sample(&self) -> Sample {
+        let mut state = self.state.lock().unwrap();
+        match self.kind {
+            RuleKind::Ratio(ratio) if ratio >= 1.0 => Sample::Keep,
+            RuleKind::Ratio(ratio) if ratio <= 0.0 => Sample::Drop,
+            RuleKind::Ratio(ratio) => {
+                let n = state.seen as f64;
+                state.seen += 1;
+                // Keep whenever the running count of kept entries ticks over
+                if ((n + 1.0) * ratio).ceil() > (n * ratio).ceil() {
+                    Sample::KeepAt(ratio)
+                } else {
+                    Sample::Drop
+                }
+            }
+            RuleKind::FirstThenEvery { first, every } => {
+                let now = Instant::now();
+                if now.duration_since(state.window_started) >= BURST_WINDOW {
+                    state.window_started = now;
+                    state.seen_in_window = 0;
+                }
+                let n = state.seen_in_window;
+                state.seen_in_window += 1;
+
+                if n < u64::from(first) || every <= 1 {
+                    Sample::Keep
+                } else if (n - u64::from(first)).is_multiple_of(u64::from(every)) {
+                    Sample::KeepAt(1.0 / f64::from(every))
+                } else {
+                    Sample::Drop
+                }
+            }
+        }
+    }
+}
+
+impl Sampler {
+    /// Creates a sampler that keeps everything until rules are added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps `ratio` of the entries at `level`, e.g. `0.01` for 1%.
+    pub fn keep_ratio(mut self, level: impl Into<String>, ratio: f64) -> Self {
+        self.rules
+            .insert(level.into(), Rule::new(RuleKind::Ratio(ratio)));
+        self
+    }
+
+    /// Keeps the first `first` entries at `level` in each second, then 1 in
+    /// every `every` of the rest.
+    pub fn keep_first_then_every(
+        mut self,
+        level: impl Into<String>,
+        first: u32,
+        every: u32,
+    ) -> Self {
+        self.rules.insert(
+            level.into(),
+            Rule::new(RuleKind::FirstThenEvery { first, every }),
+        );
+        self
+    }
+
+    /// Keeps every entry whose meta has `key`, whatever its level's rule says.
+    pub fn always_keep_key(mut self, key: impl Into<String>) -> Self {
+        self.keep_keys.push(key.into());
+        self
+    }
+
+    /// Decides whether `entry` is kept.
+    pub(crate) fn sample(&self, entry: &LogInfo) -> Sample {
+        let Some(rule) = self.rules.get(&entry.level) else {
+            return Sample::Keep;
+        };
+        if self
+            .keep_keys
+            .iter()
+            .any(|key| entry.meta.contains_key(key))
+        {
+            return Sample::Keep;
+        }
+        rule.sample()
+    }
+
+    /// Applies the sampling decision, returning the entry to keep with its
+    /// `sample_rate` set, or `None` if it was sampled out.
+    pub(crate) fn apply(&self, entry: LogInfo) -> Option<LogInfo> {
+        match self.sample(&entry) {
+            Sample::Keep => Some(entry),
+            Sample::KeepAt(rate) => Some(Self::with_rate(entry, rate)),
+            Sample::Drop => None,
+        }
+    }
+
+    /// Records the rate an entry was sampled at in its `sample_rate` meta field.
+    pub(crate) fn with_rate(mut entry: LogInfo, rate: f64) -> LogInfo {
+        entry.meta.insert("sample_rate".to_string(), rate.into());
+        entry
+    }
+}
+
+impl fmt::Debug for Sampler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: HashMap<_, _> = self
+            .rules
+            .iter()
+            .map(|(level, rule)| (level, &rule.kind))
+            .collect();
+        f.debug_struct("Sampler")
+            .field("rules", &rules)
+            .field("keep_keys", &self.keep_keys)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_kept(sampler: &Sampler, entry: &LogInfo, total: usize) -> usize {
+        (0..total)
+            .filter(|_| sampler.sample(entry) != Sample::Drop)
+            .count()
+    }
+
+    #[test]
+    fn test_ratio_keeps_evenly_spaced_entries() {
+        let sampler = Sampler::new().keep_ratio("debug", 0.01);
+        let entry = LogInfo::new("debug", "Tick");
+
+        assert_eq!(sampler.sample(&entry), Sample::KeepAt(0.01));
+        assert_eq!(count_kept(&sampler, &entry, 999), 9);
+    }
+
+    #[test]
+    fn test_first_then_every() {
+        let sampler = Sampler::new().keep_first_then_every("trace", 3, 4);
+        let entry = LogInfo::new("trace", "Tick");
+
+        let decisions: Vec<_> = (0..8).map(|_| sampler.sample(&entry)).collect();
+        assert_eq!(
+            decisions,
+            [
+                Sample::Keep,
+                Sample::Keep,
+                Sample::Keep,
+                Sample::KeepAt(0.25),
+                Sample::Drop,
+                Sample::Drop,
+                Sample::Drop,
+                Sample::KeepAt(0.25),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_always_keep_key_and_unsampled_levels() {
+        let sampler = Sampler::new()
+            .keep_ratio("debug", 0.0)
+            .always_keep_key("trace_id");
+
+        assert_eq!(sampler.sample(&LogInfo::new("debug", "Tick")), Sample::Drop);
+        assert_eq!(
+            sampler.sample(&LogInfo::new("debug", "Tick").with_meta("trace_id", "abc")),
+            Sample::Keep
+        );
+        assert_eq!(sampler.sample(&LogInfo::new("info", "Tick")), Sample::Keep);
+    }
+}
@@ -0,0 +1,105 @@
+use logform::LogInfo;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+#[derive(Serialize, Deserialize)]
+struct SpooledEntry {
+    level: String,
+    message: String,
+    meta: HashMap<String, Value>,
+}
+
+/// An on-disk queue for entries that overflowed the channel under
+/// [`BackpressureStrategy::SpillToFile`](crate::BackpressureStrategy::SpillToFile).
+///
+/// Entries are appended as JSON lines. The worker takes them back once the
+/// channel has drained. Entries left over from a previous run are replayed too.
+#[derive(Debug)]
+pub(crate) struct Spool {
+    path: PathBuf,
+    file: Mutex<Option<File>>,
+    pending: AtomicBool,
+}
+
+impl Spool {
+    pub(crate) fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let leftover = std::fs::metadata(&path).is_ok_and(|m| m.len() > 0);
+        Spool {
+            path,
+            file: Mutex::new(None),
+            pending: AtomicBool::new(leftover),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn has_pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// Appends `entry` to the spool file.
+    pub(crate) fn write(&self, entry: &LogInfo) -> io::Result<()> {
+        let line = serde_json::to_string(&SpooledEntry {
+            level: entry.level.clone(),
+            message: entry.message.clone(),
+            meta: entry.meta.clone(),
+        })?;
+
+        let mut file = self.file.lock().unwrap();
+        let file = self.open(&mut file)?;
+        writeln!(file, "{}", line)?;
+        self.pending.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Reads back every spooled entry and empties the file.
+    pub(crate) fn take(&self) -> io::Result<Vec<LogInfo>> {
+        let mut file = self.file.lock().unwrap();
+        let file = self.open(&mut file)?;
+
+        file.seek(SeekFrom::Start(0))?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(&*file).lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            // Skip lines that can't be parsed, e.g. one cut short by a crash
+            if let Ok(spooled) = serde_json::from_str::<SpooledEntry>(&line) {
+                let mut entry = LogInfo::new(spooled.level, spooled.message);
+                entry.meta = spooled.meta;
+                entries.push(entry);
+            }
+        }
+
+        file.set_len(0)?;
+        self.pending.store(false, Ordering::Release);
+        Ok(entries)
+    }
+
+    fn open<'a>(&self, file: &'a mut Option<File>) -> io::Result<&'a mut File> {
+        if file.is_none() {
+            *file = Some(
+                OpenOptions::new()
+                    .create(true)
+                    .read(true)
+                    .append(true)
+                    .open(&self.path)?,
+            );
+        }
+        Ok(file.as_mut().expect("spool file was just opened"))
+    }
+}
@@ -0,0 +1,134 @@
+use crate::logger::TransportHandle;
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A snapshot of a logger's counters, returned by [`Logger::stats`](crate::Logger::stats).
+///
+/// Counters are cumulative since the logger was created. The backpressure drop
+/// counters include entries dropped by the queues of transports with a
+/// [dedicated worker](crate::LoggerTransport::with_dedicated_worker).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoggerStats {
+    /// Entries that passed the level check and sampling and were handed to the channel.
+    pub accepted: u64,
+    /// Entries rejected because no transport accepts their level.
+    pub filtered: u64,
+    /// Queued entries evicted by [`BackpressureStrategy::DropOldest`](crate::BackpressureStrategy::DropOldest),
+    /// plus new entries it still could not queue.
+    pub dropped_oldest: u64,
+    /// Entries discarded by [`BackpressureStrategy::DropCurrent`](crate::BackpressureStrategy::DropCurrent).
+    pub dropped_current: u64,
+    /// Entries discarded after [`BackpressureStrategy::BlockTimeout`](crate::BackpressureStrategy::BlockTimeout) expired.
+    pub dropped_timeout: u64,
+    /// Entries skipped by [`BackpressureStrategy::Sample`](crate::BackpressureStrategy::Sample).
+    pub dropped_sampled: u64,
+    /// Entries written to the [`BackpressureStrategy::SpillToFile`](crate::BackpressureStrategy::SpillToFile) spool.
+    pub spilled: u64,
+    /// Entries lost because the spool could not be written.
+    pub spill_failed: u64,
+    /// Entries at or above the [`priority_level`](crate::LoggerOptions::priority_level),
+    /// queued on the priority lane instead of being subject to backpressure.
+    pub prioritized: u64,
+    /// Entries dropped by a [`Sampler`](crate::Sampler), at the logger or a transport.
+    pub sampled_out: u64,
+    /// Repeats held back by a [`RateLimiter`](crate::RateLimiter), at the
+    /// logger or a transport, and later written as one entry.
+    pub suppressed_repeats: u64,
+    /// Entries dropped by a [`RateLimiter`](crate::RateLimiter) token bucket,
+    /// at the logger or a transport.
+    pub rate_limited: u64,
+    /// Entries held back because no transport was configured.
+    pub buffered: u64,
+    /// Buffered entries dropped or spilled to stderr because the buffer was full.
+    pub buffer_overflow: u64,
+    /// Entries written to each transport.
+    pub delivered: HashMap<TransportHandle, u64>,
+    /// Messages currently waiting in the channel and the priority lane.
+    pub channel_depth: usize,
+    /// Entries currently held in the no-transport buffer.
+    pub buffer_len: usize,
+}
+
+impl LoggerStats {
+    /// Total entries dropped by backpressure. Spilled entries are not lost, so
+    /// only failed spills count.
+    pub fn dropped(&self) -> u64 {
+        self.dropped_oldest
+            + self.dropped_current
+            + self.dropped_timeout
+            + self.dropped_sampled
+            + self.spill_failed
+    }
+}
+
+/// Live counters shared between the logger and its worker.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    pub(crate) accepted: AtomicU64,
+    pub(crate) filtered: AtomicU64,
+    pub(crate) dropped_oldest: AtomicU64,
+    pub(crate) dropped_current: AtomicU64,
+    pub(crate) dropped_timeout: AtomicU64,
+    pub(crate) dropped_sampled: AtomicU64,
+    pub(crate) spilled: AtomicU64,
+    pub(crate) spill_failed: AtomicU64,
+    pub(crate) prioritized: AtomicU64,
+    pub(crate) sampled_out: AtomicU64,
+    pub(crate) suppressed_repeats: AtomicU64,
+    pub(crate) rate_limited: AtomicU64,
+    pub(crate) buffered: AtomicU64,
+    pub(crate) buffer_overflow: AtomicU64,
+    delivered: RwLock<HashMap<TransportHandle, AtomicU64>>,
+}
+
+impl StatsCounters {
+    pub(crate) fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_delivery(&self, handle: TransportHandle) {
+        self.record_deliveries(handle, 1);
+    }
+
+    pub(crate) fn record_deliveries(&self, handle: TransportHandle, count: u64) {
+        if let Some(delivered) = self.delivered.read().get(&handle) {
+            delivered.fetch_add(count, Ordering::Relaxed);
+            return;
+        }
+        self.delivered
+            .write()
+            .entry(handle)
+            .or_default()
+            .fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self, channel_depth: usize, buffer_len: usize) -> LoggerStats {
+        LoggerStats {
+            accepted: self.accepted.load(Ordering::Relaxed),
+            filtered: self.filtered.load(Ordering::Relaxed),
+            dropped_oldest: self.dropped_oldest.load(Ordering::Relaxed),
+            dropped_current: self.dropped_current.load(Ordering::Relaxed),
+            dropped_timeout: self.dropped_timeout.load(Ordering::Relaxed),
+            dropped_sampled: self.dropped_sampled.load(Ordering::Relaxed),
+            spilled: self.spilled.load(Ordering::Relaxed),
+            spill_failed: self.spill_failed.load(Ordering::Relaxed),
+            prioritized: self.prioritized.load(Ordering::Relaxed),
+            sampled_out: self.sampled_out.load(Ordering::Relaxed),
+            suppressed_repeats: self.suppressed_repeats.load(Ordering::Relaxed),
+            rate_limited: self.rate_limited.load(Ordering::Relaxed),
+            buffered: self.buffered.load(Ordering::Relaxed),
+            buffer_overflow: self.buffer_overflow.load(Ordering::Relaxed),
+            delivered: self
+                .delivered
+                .read()
+                .iter()
+                .map(|(handle, count)| (*handle, count.load(Ordering::Relaxed)))
+                .collect(),
+            channel_depth,
+            buffer_len,
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use winston_transport::{LogQuery, Transport};
+
+const DEFAULT_MAX_FAILURES: u32 = 3;
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+const DEFAULT_CHECK_EVERY: u64 = 100;
+
+struct Member<L> {
+    transport: Arc<dyn Transport<L> + Send + Sync>,
+    health: Mutex<Health>,
+    // Set when an entry has been written since the last flush
+    written: AtomicBool,
+    // Entries written since the last successful flush, replayed on failover
+    unconfirmed: Mutex<VecDeque<L>>,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    unhealthy_since: Option<Instant>,
+}
+
+/// A transport that writes to the first healthy transport in a chain.
+///
+/// Entries go to the primary while it is healthy and to the next healthy
+/// secondary otherwise. Because [`Transport::log`] cannot report errors,
+/// health is judged by `flush`: after `max_failures` consecutive failed
+/// flushes a transport is marked unhealthy and skipped. Once `cooldown` has
+/// passed it receives entries again as a probe, and the next flush decides
+/// whether it has recovered.
+///
+/// The logger only flushes its transports when asked to, so the failover
+/// transport also checks health itself, by flushing after every
+/// [`check_every`](Self::with_check_every) entries (100 by default). Until a
+/// check or an explicit flush fails, a dead transport keeps receiving
+/// entries. Each transport's entries are kept until a flush succeeds, and
+/// when it is marked unhealthy they are written again to the next healthy
+/// transport. At most `max_failures` × `check_every` entries are kept per
+/// transport (counting `check_every` as 100 when checks are off); older
+/// ones are lost if it fails.
+///
+/// # Example
+/// ```ignore
+/// let shipping = FailoverTransport::new(HttpTransport::new(collector_url))
+///     .with_secondary(FileTransport::new("backup.log"))
+///     .with_max_failures(2)
+///     .with_cooldown(Duration::from_secs(60));
+///
+/// let logger = Logger::builder()
+///     .transport(LoggerTransport::new(shipping).with_level("info"))
+///     .build();
+/// ```
+pub struct FailoverTransport<L> {
+    members: Vec<Member<L>>,
+    max_failures: u32,
+    cooldown: Duration,
+    check_every: u64,
+    // Entries written since health was last checked
+    unchecked: AtomicU64,
+}
+
+impl<L> FailoverTransport<L> {
+    /// Creates a failover chain with `primary` as its preferred transport.
+    pub fn new<T>(primary: T) -> Self
+    where
+        T: Transport<L> + Send + Sync + 'static,
+    {
+        FailoverTransport {
+            members: Vec::new(),
+            max_failures: DEFAULT_MAX_FAILURES,
+            cooldown: DEFAULT_COOLDOWN,
+            check_every: DEFAULT_CHECK_EVERY,
+            unchecked: AtomicU64::new(0),
+        }
+        .with_secondary(primary)
+    }
+
+    /// Appends a transport used when every transport before it is unhealthy.
+    pub fn with_secondary<T>(mut self, transport: T) -> Self
+    where
+        T: Transport<L> + Send + Sync + 'static,
+    {
+        self.members.push(Member {
+            transport: Arc::new(transport),
+            health: Mutex::new(Health::default()),
+            written: AtomicBool::new(false),
+            unconfirmed: Mutex::new(VecDeque::new()),
+        });
+        self
+    }
+
+    /// Sets how many consecutive failures mark a transport unhealthy (default 3).
+    pub fn with_max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    /// Sets how long an unhealthy transport is skipped before it is probed again (default 30s).
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Sets after how many entries the chain is flushed to check its health
+    /// (default 100). `0` turns the checks off, leaving them to explicit flushes.
+    pub fn with_check_every(mut self, entries: u64) -> Self {
+        self.check_every = entries;
+        self
+    }
+
+    /// Returns whether the transport at `index` (0 being the primary) is currently healthy.
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.members
+            .get(index)
+            .is_some_and(|member| member.health.lock().unhealthy_since.is_none())
+    }
+
+    /// Returns the index of the transport that would receive the next entry.
+    pub fn active_index(&self) -> Option<usize> {
+        let now = Instant::now();
+        self.members
+            .iter()
+            .position(|member| self.is_available(member, now))
+    }
+
+    fn is_available(&self, member: &Member<L>, now: Instant) -> bool {
+        match member.health.lock().unhealthy_since {
+            None => true,
+            Some(since) => now.duration_since(since) >= self.cooldown,
+        }
+    }
+
+    /// Picks the member for the next write.
+    fn select_member(&self) -> &Member<L> {
+        let now = Instant::now();
+        // When every transport is unhealthy, keep trying the primary rather than dropping
+        self.members
+            .iter()
+            .find(|member| self.is_available(member, now))
+            .unwrap_or(&self.members[0])
+    }
+
+    fn record_success(&self, member: &Member<L>) {
+        *member.health.lock() = Health::default();
+    }
+
+    /// Records a failed flush, returning whether it marked the member unhealthy.
+    fn record_failure(&self, member: &Member<L>) -> bool {
+        let mut health = member.health.lock();
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.max_failures {
+            // A failed probe starts a new cooldown
+            health.unhealthy_since = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    /// How many unconfirmed entries each member keeps for replay.
+    fn replay_limit(&self) -> usize {
+        let check_every = match self.check_every {
+            0 => DEFAULT_CHECK_EVERY,
+            entries => entries,
+        };
+        usize::try_from(u64::from(self.max_failures).saturating_mul(check_every))
+            .unwrap_or(usize::MAX)
+    }
+}
+
+impl<L: Clone> FailoverTransport<L> {
+    /// Writes `logs` to `member`, keeping them until its next successful flush.
+    fn write(&self, member: &Member<L>, logs: Vec<L>) {
+        {
+            let mut unconfirmed = member.unconfirmed.lock();
+            unconfirmed.extend(logs.iter().cloned());
+            let excess = unconfirmed.len().saturating_sub(self.replay_limit());
+            unconfirmed.drain(..excess);
+        }
+        member.written.store(true, Ordering::Release);
+        match <[L; 1]>::try_from(logs) {
+            Ok([info]) => member.transport.log(info),
+            Err(logs) => member.transport.log_batch(logs),
+        }
+    }
+
+    /// Counts `written` entries, flushing to check health once enough have been written.
+    fn count_writes(&self, written: u64) {
+        if self.check_every == 0 {
+            return;
+        }
+        let unchecked = self.unchecked.fetch_add(written, Ordering::AcqRel) + written;
+        if unchecked >= self.check_every {
+            self.unchecked.store(0, Ordering::Release);
+            // Failures are recorded in the members' health; the caller can't act on them
+            let _ = self.flush();
+        }
+    }
+
+    /// Writes the entries `failed` never confirmed to the next healthy member.
+    fn replay(&self, failed: usize) {
+        let now = Instant::now();
+        let Some(target) = self
+            .members
+            .iter()
+            .enumerate()
+            .find(|(index, member)| *index != failed && self.is_available(member, now))
+            .map(|(_, member)| member)
+        else {
+            // Nowhere to go; they stay with the failed member for its next probe
+            return;
+        };
+
+        let logs: Vec<L> = self.members[failed].unconfirmed.lock().drain(..).collect();
+        if !logs.is_empty() {
+            self.write(target, logs);
+        }
+    }
+}
+
+impl<L: Clone> Transport<L> for FailoverTransport<L> {
+    fn log(&self, info: L) {
+        self.write(self.select_member(), vec![info]);
+        self.count_writes(1);
+    }
+
+    fn log_batch(&self, logs: Vec<L>) {
+        let written = logs.len() as u64;
+        self.write(self.select_member(), logs);
+        self.count_writes(written);
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        let mut failures = Vec::new();
+
+        // Members are flushed in order, so one that entries are replayed to
+        // is flushed in the same pass
+        for (index, member) in self.members.iter().enumerate() {
+            if !member.written.swap(false, Ordering::AcqRel) {
+                continue;
+            }
+            let written = member.unconfirmed.lock().len();
+            match member.transport.flush() {
+                Ok(()) => {
+                    self.record_success(member);
+                    let mut unconfirmed = member.unconfirmed.lock();
+                    let confirmed = written.min(unconfirmed.len());
+                    unconfirmed.drain(..confirmed);
+                }
+                Err(e) => {
+                    if self.record_failure(member) {
+                        self.replay(index);
+                    }
+                    failures.push(format!("failover transport {}: {}", index, e));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; "))
+        }
+    }
+
+    fn query(&self, options: &LogQuery) -> Result<Vec<L>, String> {
+        let mut results = Vec::new();
+        let mut first_error = None;
+        let mut any_succeeded = false;
+
+        for member in &self.members {
+            match member.transport.query(options) {
+                Ok(mut logs) => {
+                    any_succeeded = true;
+                    results.append(&mut logs);
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        match first_error {
+            Some(e) if !any_succeeded => Err(e),
+            _ => Ok(results),
+        }
+    }
+}
+
+impl<L> fmt::Debug for FailoverTransport<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let health: Vec<_> = self
+            .members
+            .iter()
+            .map(|member| member.health.lock().unhealthy_since.is_none())
+            .collect();
+        f.debug_struct("FailoverTransport")
+            .field("healthy", &health)
+            .field("max_failures", &self.max_failures)
+            .field("cooldown", &self.cooldown)
+            .field("check_every", &self.check_every)
+            .finish()
+    }
+}
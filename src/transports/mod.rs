@@ -1,3 +1,9 @@
+mod failover;
+mod queued;
+
+pub use failover::FailoverTransport;
+pub(crate) use queued::QueueReporting;
+pub use queued::QueuedTransport;
 use std::io;
 pub use winston_file::FileTransport as File;
 pub use winston_transport::transport_adapters::WriterTransport;
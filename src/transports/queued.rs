@@ -0,0 +1,291 @@
+use crate::{diagnostics::DiagnosticKind, stats::StatsCounters, BackpressureStrategy, Diagnostics};
+use crossbeam_channel::{bounded, Receiver, Sender, TrySendError};
+use parking_lot::RwLock;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+use winston_transport::{LogQuery, Transport};
+
+enum QueueMessage<L> {
+    Entry(L),
+    Flush(Sender<Result<(), String>>),
+    Query(LogQuery, Sender<Result<Vec<L>, String>>),
+    Shutdown,
+}
+
+/// The sink and counters of the logger a dedicated worker's transport was
+/// added to, which the queue reports its drops to.
+#[derive(Debug, Default)]
+pub(crate) struct QueueReporting {
+    diagnostics: RwLock<Option<Diagnostics>>,
+    stats: RwLock<Option<Arc<StatsCounters>>>,
+}
+
+impl QueueReporting {
+    pub(crate) fn attach(&self, diagnostics: Option<&Diagnostics>, stats: &Arc<StatsCounters>) {
+        *self.diagnostics.write() = diagnostics.cloned();
+        *self.stats.write() = Some(Arc::clone(stats));
+    }
+}
+
+/// A transport wrapper that delivers entries to the inner transport on its own
+/// thread, through a bounded queue with its own backpressure strategy.
+///
+/// With [`DropCurrent`](BackpressureStrategy::DropCurrent) or
+/// [`DropOldest`](BackpressureStrategy::DropOldest), a slow transport wrapped
+/// this way no longer stalls the logger's worker or the other transports; the
+/// other strategies make the worker wait while the queue is full. The queue
+/// has no spill file, so [`SpillToFile`](BackpressureStrategy::SpillToFile)
+/// waits for room like [`Block`](BackpressureStrategy::Block). [`flush`](Transport::flush) waits until everything
+/// queued before it has been written and the inner transport has flushed, so
+/// [`Logger::flush`](crate::Logger::flush) and [`Logger::close`](crate::Logger::close)
+/// still wait for every queue. Dropping the wrapper drains the queue and stops
+/// the thread.
+///
+/// Usually created through [`LoggerTransport::with_dedicated_worker`](crate::LoggerTransport::with_dedicated_worker).
+pub struct QueuedTransport<L: Send + 'static> {
+    inner: Arc<dyn Transport<L> + Send + Sync>,
+    sender: Sender<QueueMessage<L>>,
+    receiver: Receiver<QueueMessage<L>>,
+    strategy: BackpressureStrategy,
+    diagnostics: Diagnostics,
+    reporting: Arc<QueueReporting>,
+    dropped: AtomicU64,
+    sample_counter: AtomicU64,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<L: Send + 'static> QueuedTransport<L> {
+    pub fn new<T>(transport: T, capacity: usize, strategy: BackpressureStrategy) -> Self
+    where
+        T: Transport<L> + Send + Sync + 'static,
+    {
+        Self::from_arc(Arc::new(transport), capacity, strategy, Arc::default())
+    }
+
+    pub(crate) fn from_arc(
+        inner: Arc<dyn Transport<L> + Send + Sync>,
+        capacity: usize,
+        strategy: BackpressureStrategy,
+        reporting: Arc<QueueReporting>,
+    ) -> Self {
+        let (sender, receiver) = bounded(capacity);
+
+        let worker_inner = Arc::clone(&inner);
+        let worker_receiver = receiver.clone();
+        let worker = thread::Builder::new()
+            .name("winston-transport".to_string())
+            .spawn(move || Self::worker_loop(worker_inner, worker_receiver))
+            .expect("Failed to spawn transport worker thread");
+
+        QueuedTransport {
+            inner,
+            sender,
+            receiver,
+            strategy,
+            diagnostics: Diagnostics::default(),
+            reporting,
+            dropped: AtomicU64::new(0),
+            sample_counter: AtomicU64::new(0),
+            worker: Mutex::new(Some(worker)),
+        }
+    }
+
+    fn worker_loop(
+        transport: Arc<dyn Transport<L> + Send + Sync>,
+        receiver: Receiver<QueueMessage<L>>,
+    ) {
+        for message in receiver.iter() {
+            match message {
+                QueueMessage::Entry(info) => transport.log(info),
+                QueueMessage::Flush(response) => {
+                    let _ = response.send(transport.flush());
+                }
+                QueueMessage::Query(query, response) => {
+                    let _ = response.send(transport.query(&query));
+                }
+                QueueMessage::Shutdown => {
+                    let _ = transport.flush();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Sets where dropped entries are reported; stderr by default.
+    ///
+    /// A queue created by [`LoggerTransport::with_dedicated_worker`](crate::LoggerTransport::with_dedicated_worker)
+    /// reports to the diagnostics sink of the logger it is added to instead,
+    /// when that logger has one.
+    pub fn with_diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Returns the number of entries waiting to be written.
+    pub fn queue_len(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Returns the wrapped transport.
+    pub fn inner(&self) -> &Arc<dyn Transport<L> + Send + Sync> {
+        &self.inner
+    }
+
+    /// Returns how many entries this queue has dropped because it was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Counts a dropped entry here and in the logger's `counter`, if the queue
+    /// belongs to a logger.
+    fn record_drop(&self, counter: fn(&StatsCounters) -> &AtomicU64, message: &str) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        if let Some(stats) = self.reporting.stats.read().as_ref() {
+            StatsCounters::increment(counter(stats));
+        }
+        self.report(DiagnosticKind::Drop, || message.to_string());
+    }
+
+    fn report(&self, kind: DiagnosticKind, message: impl FnOnce() -> String) {
+        let logger_diagnostics = self.reporting.diagnostics.read();
+        logger_diagnostics
+            .as_ref()
+            .unwrap_or(&self.diagnostics)
+            .report(kind, message);
+    }
+
+    fn handle_full_queue(&self, info: L) {
+        match &self.strategy {
+            // A per-transport queue has no spool of its own, so spilling waits for room
+            BackpressureStrategy::Block | BackpressureStrategy::SpillToFile(_) => {
+                let _ = self.sender.send(QueueMessage::Entry(info));
+            }
+            BackpressureStrategy::BlockTimeout(timeout) => {
+                if self
+                    .sender
+                    .send_timeout(QueueMessage::Entry(info), *timeout)
+                    .is_err()
+                {
+                    self.record_drop(
+                        |stats| &stats.dropped_timeout,
+                        "Dropping log entry after waiting for transport queue space.",
+                    );
+                }
+            }
+            BackpressureStrategy::Sample(n) => {
+                let seen = self.sample_counter.fetch_add(1, Ordering::Relaxed);
+                if *n <= 1 || seen.is_multiple_of(u64::from(*n)) {
+                    let _ = self.sender.send(QueueMessage::Entry(info));
+                } else {
+                    self.record_drop(
+                        |stats| &stats.dropped_sampled,
+                        "Sampling entries while the transport queue is full.",
+                    );
+                }
+            }
+            BackpressureStrategy::DropCurrent => {
+                self.record_drop(
+                    |stats| &stats.dropped_current,
+                    "Dropping current log entry due to full transport queue.",
+                );
+            }
+            BackpressureStrategy::DropOldest => {
+                // Control messages are never dropped. Any popped on the way to
+                // the oldest entry are put back behind the pending entries,
+                // dropping further entries if the freed slots are taken first.
+                let mut controls = VecDeque::new();
+                let mut need_room = true;
+                while need_room || !controls.is_empty() {
+                    if !need_room {
+                        if let Some(control) = controls.pop_front() {
+                            match self.sender.try_send(control) {
+                                Ok(()) | Err(TrySendError::Disconnected(_)) => continue,
+                                Err(TrySendError::Full(control)) => controls.push_front(control),
+                            }
+                        }
+                    }
+                    match self.receiver.try_recv() {
+                        Ok(QueueMessage::Entry(_)) => {
+                            need_room = false;
+                            self.record_drop(
+                                |stats| &stats.dropped_oldest,
+                                "Dropped oldest log entry due to full transport queue.",
+                            );
+                        }
+                        Ok(control) => controls.push_back(control),
+                        Err(_) => need_room = false,
+                    }
+                }
+
+                if self.sender.try_send(QueueMessage::Entry(info)).is_err() {
+                    self.record_drop(
+                        |stats| &stats.dropped_oldest,
+                        "Failed to queue log after dropping oldest. Dropping current message.",
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl<L: Send + 'static> Transport<L> for QueuedTransport<L> {
+    fn log(&self, info: L) {
+        match self.sender.try_send(QueueMessage::Entry(info)) {
+            Ok(()) => {}
+            Err(TrySendError::Full(QueueMessage::Entry(info))) => self.handle_full_queue(info),
+            Err(TrySendError::Full(_)) => unreachable!("only entries are sent here"),
+            Err(TrySendError::Disconnected(_)) => {
+                self.report(DiagnosticKind::Disconnected, || {
+                    "Transport worker is disconnected. Unable to log message.".to_string()
+                });
+            }
+        }
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        let (response_sender, response_receiver) = bounded(1);
+        self.sender
+            .send(QueueMessage::Flush(response_sender))
+            .map_err(|_| "Failed to send flush message to transport worker".to_string())?;
+        response_receiver
+            .recv()
+            .map_err(|_| "Transport worker stopped before flushing".to_string())?
+    }
+
+    fn query(&self, options: &LogQuery) -> Result<Vec<L>, String> {
+        let (response_sender, response_receiver) = bounded(1);
+        self.sender
+            .send(QueueMessage::Query(options.clone(), response_sender))
+            .map_err(|_| "Failed to send query message to transport worker".to_string())?;
+        response_receiver
+            .recv()
+            .map_err(|_| "Transport worker stopped before answering the query".to_string())?
+    }
+}
+
+impl<L: Send + 'static> Drop for QueuedTransport<L> {
+    fn drop(&mut self) {
+        let _ = self.sender.send(QueueMessage::Shutdown);
+        if let Some(handle) = self.worker.lock().unwrap().take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl<L: Send + 'static> fmt::Debug for QueuedTransport<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueuedTransport")
+            .field("capacity", &self.sender.capacity())
+            .field("queued", &self.sender.len())
+            .field("strategy", &self.strategy)
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use common::{MockConfig, MockTransport};
+use logform::LogInfo;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use winston::{BackpressureStrategy, Logger};
+
+#[tokio::test]
+async fn test_log_async_and_flush_async_deliver_entries() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder().transport(transport.clone()).build();
+
+    for i in 0..10 {
+        logger
+            .log_async(LogInfo::new("info", format!("Message {}", i)))
+            .await;
+    }
+    logger.flush_async().await.unwrap();
+
+    assert_eq!(transport.log_count(), 10);
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_log_async_waits_for_capacity_without_blocking_executor() {
+    let transport = MockTransport::with_config(MockConfig {
+        delay: Duration::from_millis(5),
+        ..Default::default()
+    });
+    let logger = Arc::new(
+        Logger::builder()
+            .channel_capacity(1)
+            .backpressure_strategy(BackpressureStrategy::Block)
+            .transport(transport.clone())
+            .build(),
+    );
+
+    // On a single-threaded runtime, this task only runs if log_async yields
+    let ticked = Arc::new(AtomicBool::new(false));
+    let ticker_flag = Arc::clone(&ticked);
+    let ticker = tokio::spawn(async move {
+        ticker_flag.store(true, Ordering::SeqCst);
+    });
+
+    for i in 0..20 {
+        logger
+            .log_async(LogInfo::new("info", format!("Message {}", i)))
+            .await;
+    }
+    assert!(ticked.load(Ordering::SeqCst));
+
+    ticker.await.unwrap();
+    logger.flush_async().await.unwrap();
+    assert_eq!(transport.log_count(), 20);
+}
+
+#[tokio::test]
+async fn test_close_async_flushes_and_stops_worker() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder().transport(transport.clone()).build();
+
+    logger.log_async(LogInfo::new("info", "Before close")).await;
+    logger.close_async().await;
+
+    assert_eq!(transport.log_count(), 1);
+
+    // Further flushes are no-ops once closed
+    assert!(logger.flush_async().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_flush_async_reports_transport_errors() {
+    let transport = MockTransport::with_config(MockConfig {
+        should_fail_flush: true,
+        ..Default::default()
+    });
+    let logger = Logger::builder().transport(transport).build();
+
+    logger.log_async(LogInfo::new("info", "Test")).await;
+
+    let error = logger.flush_async().await.unwrap_err();
+    assert!(error.contains("Mock flush failure"));
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use common::{cleanup_file, temp_log_file, MockTransport};
+use logform::LogInfo;
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use winston::{
+    transports::{QueuedTransport, Transport},
+    BackpressureStrategy, Diagnostics, Logger,
+};
+
+fn overloaded_logger(strategy: BackpressureStrategy, transport: MockTransport) -> Logger {
+    Logger::builder()
+        .channel_capacity(1)
+        .backpressure_strategy(strategy)
+        .transport(transport)
+        .diagnostics(Diagnostics::silent())
+        .build()
+}
+
+fn log_numbered(logger: &Logger, count: usize) {
+    for i in 0..count {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+}
+
+#[test]
+fn test_block_timeout_drops_after_waiting() {
+    let transport = MockTransport::with_delay(Duration::from_millis(50));
+    let logger = overloaded_logger(
+        BackpressureStrategy::BlockTimeout(Duration::from_millis(1)),
+        transport.clone(),
+    );
+
+    log_numbered(&logger, 10);
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.dropped_timeout > 0);
+    assert_eq!(stats.dropped(), stats.dropped_timeout);
+    assert_eq!(transport.log_count() as u64, 10 - stats.dropped_timeout);
+}
+
+#[test]
+fn test_block_timeout_keeps_entries_when_worker_catches_up() {
+    let transport = MockTransport::with_delay(Duration::from_millis(1));
+    let logger = overloaded_logger(
+        BackpressureStrategy::BlockTimeout(Duration::from_secs(5)),
+        transport.clone(),
+    );
+
+    log_numbered(&logger, 10);
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().dropped(), 0);
+    assert_eq!(transport.log_count(), 10);
+}
+
+#[test]
+fn test_sample_keeps_a_fraction_under_pressure() {
+    let transport = MockTransport::with_delay(Duration::from_millis(10));
+    let logger = overloaded_logger(BackpressureStrategy::Sample(4), transport.clone());
+
+    log_numbered(&logger, 40);
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.dropped_sampled > 0);
+    assert!(transport.log_count() > 1);
+    assert_eq!(transport.log_count() as u64, 40 - stats.dropped_sampled);
+}
+
+#[test]
+fn test_spill_to_file_delivers_every_entry() {
+    let spool = temp_log_file();
+    let transport = MockTransport::with_delay(Duration::from_millis(5));
+    let logger = overloaded_logger(
+        BackpressureStrategy::SpillToFile(spool.clone().into()),
+        transport.clone(),
+    );
+
+    log_numbered(&logger, 20);
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.spilled > 0);
+    assert_eq!(stats.dropped(), 0);
+    assert_eq!(transport.log_count(), 20);
+    assert!(transport.has_message("Message 19"));
+
+    logger.close();
+    cleanup_file(&spool);
+}
+
+#[test]
+fn test_spill_to_file_replays_leftover_entries() {
+    let spool = temp_log_file();
+    std::fs::write(
+        &spool,
+        "{\"level\":\"warn\",\"message\":\"From last run\",\"meta\":{}}\n",
+    )
+    .unwrap();
+
+    let transport = MockTransport::new();
+    let logger = overloaded_logger(
+        BackpressureStrategy::SpillToFile(spool.clone().into()),
+        transport.clone(),
+    );
+    logger.flush().unwrap();
+
+    assert!(transport.has_message("From last run"));
+    assert_eq!(std::fs::metadata(&spool).unwrap().len(), 0);
+
+    logger.close();
+    cleanup_file(&spool);
+}
+
+#[test]
+fn test_queued_transport_counts_drops() {
+    let transport = MockTransport::with_delay(Duration::from_millis(20));
+    let queued = QueuedTransport::new(transport.clone(), 1, BackpressureStrategy::DropCurrent)
+        .with_diagnostics(Diagnostics::silent());
+
+    for i in 0..10 {
+        queued.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    queued.flush().unwrap();
+
+    assert!(queued.dropped() > 0);
+    assert_eq!(transport.log_count() as u64, 10 - queued.dropped());
+}
+
+/// Holds each entry until the test releases the gate.
+struct GatedTransport {
+    gate: Arc<Mutex<()>>,
+    inner: MockTransport,
+}
+
+impl Transport<LogInfo> for GatedTransport {
+    fn log(&self, info: LogInfo) {
+        let _open = self.gate.lock().unwrap();
+        self.inner.log(info);
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        self.inner.flush()
+    }
+}
+
+fn wait_for_queue_len(queued: &QueuedTransport<LogInfo>, len: usize) {
+    while queued.queue_len() != len {
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn test_queued_drop_oldest_requeues_controls_and_keeps_new_entry() {
+    let gate = Arc::new(Mutex::new(()));
+    let transport = MockTransport::new();
+    let queued = Arc::new(
+        QueuedTransport::new(
+            GatedTransport {
+                gate: Arc::clone(&gate),
+                inner: transport.clone(),
+            },
+            4,
+            BackpressureStrategy::DropOldest,
+        )
+        .with_diagnostics(Diagnostics::silent()),
+    );
+
+    // The queue's worker holds the first entry at the gate
+    let closed = gate.lock().unwrap();
+    queued.log(LogInfo::new("info", "first"));
+    wait_for_queue_len(&queued, 0);
+
+    // Two flushes ahead of two entries fill the queue
+    let flushes: Vec<_> = (1..=2)
+        .map(|len| {
+            let flushing = Arc::clone(&queued);
+            let flush = thread::spawn(move || flushing.flush());
+            wait_for_queue_len(&queued, len);
+            flush
+        })
+        .collect();
+    queued.log(LogInfo::new("info", "second"));
+    queued.log(LogInfo::new("info", "third"));
+    assert_eq!(queued.queue_len(), 4);
+
+    // Room is made by dropping the oldest entry, not the flushes or the new entry
+    queued.log(LogInfo::new("info", "fourth"));
+    assert_eq!(queued.dropped(), 1);
+    assert_eq!(queued.queue_len(), 4);
+
+    drop(closed);
+    for flush in flushes {
+        flush.join().unwrap().unwrap();
+    }
+    queued.flush().unwrap();
+
+    let messages: Vec<_> = transport
+        .get_logs()
+        .into_iter()
+        .map(|entry| entry.message)
+        .collect();
+    assert_eq!(messages, ["first", "third", "fourth"]);
+}
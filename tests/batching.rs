@@ -0,0 +1,141 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use winston::{Diagnostics, Logger, LoggerTransport};
+use winston_transport::Transport;
+
+/// Records the size of every batch it is handed.
+#[derive(Clone, Default)]
+struct BatchRecordingTransport {
+    batches: Arc<Mutex<Vec<usize>>>,
+    logs: Arc<Mutex<Vec<LogInfo>>>,
+}
+
+impl BatchRecordingTransport {
+    fn batch_sizes(&self) -> Vec<usize> {
+        self.batches.lock().unwrap().clone()
+    }
+
+    fn messages(&self) -> Vec<String> {
+        self.logs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|e| e.message.clone())
+            .collect()
+    }
+}
+
+impl Transport<LogInfo> for BatchRecordingTransport {
+    fn log(&self, info: LogInfo) {
+        self.log_batch(vec![info]);
+    }
+
+    fn log_batch(&self, logs: Vec<LogInfo>) {
+        self.batches.lock().unwrap().push(logs.len());
+        self.logs.lock().unwrap().extend(logs);
+    }
+}
+
+fn batching_logger(size: usize, latency: Duration) -> Logger {
+    Logger::builder()
+        .format(logform::passthrough())
+        .batch_size(size)
+        .batch_latency(latency)
+        .diagnostics(Diagnostics::silent())
+        .build()
+}
+
+fn log_numbered(logger: &Logger, count: usize) {
+    for i in 0..count {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+}
+
+#[test]
+fn test_entries_are_delivered_in_batches() {
+    let logger = batching_logger(10, Duration::from_millis(50));
+    let transport = BatchRecordingTransport::default();
+    logger.add_transport(transport.clone());
+
+    log_numbered(&logger, 100);
+    logger.flush().unwrap();
+
+    let sizes = transport.batch_sizes();
+    assert_eq!(sizes.iter().sum::<usize>(), 100);
+    assert!(sizes.iter().all(|&size| size <= 10));
+    assert!(sizes.len() < 100, "expected batching, got {:?}", sizes);
+
+    let expected: Vec<_> = (0..100).map(|i| format!("Message {}", i)).collect();
+    assert_eq!(transport.messages(), expected);
+}
+
+#[test]
+fn test_latency_waits_for_batch_to_fill() {
+    let logger = batching_logger(3, Duration::from_millis(500));
+    let transport = BatchRecordingTransport::default();
+    logger.add_transport(transport.clone());
+
+    log_numbered(&logger, 2);
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(transport.batch_sizes().is_empty());
+
+    log_numbered(&logger, 1);
+    logger.flush().unwrap();
+    assert_eq!(transport.batch_sizes(), [3]);
+}
+
+#[test]
+fn test_flush_delivers_partial_batch() {
+    let logger = batching_logger(50, Duration::from_secs(10));
+    let transport = BatchRecordingTransport::default();
+    logger.add_transport(transport.clone());
+
+    log_numbered(&logger, 5);
+    logger.flush().unwrap();
+
+    assert_eq!(transport.messages().len(), 5);
+}
+
+#[test]
+fn test_transports_without_batch_support_get_each_entry() {
+    let logger = batching_logger(10, Duration::from_millis(20));
+    let transport = MockTransport::new();
+    let handle = logger.add_transport(transport.clone());
+
+    log_numbered(&logger, 25);
+    logger.flush().unwrap();
+
+    assert_eq!(transport.log_count(), 25);
+    assert_eq!(logger.stats().delivered.get(&handle), Some(&25));
+}
+
+#[test]
+fn test_batches_respect_transport_levels() {
+    let logger = batching_logger(10, Duration::from_millis(20));
+    let all = BatchRecordingTransport::default();
+    let errors = BatchRecordingTransport::default();
+    logger.add_transport(all.clone());
+    logger.add_transport(LoggerTransport::new(errors.clone()).with_level("error"));
+
+    for i in 0..10 {
+        let level = if i % 2 == 0 { "info" } else { "error" };
+        logger.log(LogInfo::new(level, format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(all.messages().len(), 10);
+    assert_eq!(
+        errors.messages(),
+        [
+            "Message 1",
+            "Message 3",
+            "Message 5",
+            "Message 7",
+            "Message 9"
+        ]
+    );
+}
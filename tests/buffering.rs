@@ -0,0 +1,88 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use winston::{BufferOverflowPolicy, Diagnostics, Logger};
+
+fn buffering_logger(capacity: usize, policy: BufferOverflowPolicy) -> Logger {
+    Logger::builder()
+        .format(logform::passthrough())
+        .buffer_capacity(capacity)
+        .buffer_overflow(policy)
+        .diagnostics(Diagnostics::silent())
+        .build()
+}
+
+fn log_numbered(logger: &Logger, count: usize) {
+    for i in 0..count {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+}
+
+fn messages(entries: &[LogInfo]) -> Vec<&str> {
+    entries.iter().map(|e| e.message.as_str()).collect()
+}
+
+#[test]
+fn test_drop_oldest_keeps_most_recent_entries() {
+    let logger = buffering_logger(3, BufferOverflowPolicy::DropOldest);
+    log_numbered(&logger, 5);
+
+    let buffered = logger.buffered_entries();
+    assert_eq!(messages(&buffered), ["Message 2", "Message 3", "Message 4"]);
+
+    let stats = logger.stats();
+    assert_eq!(stats.buffered, 5);
+    assert_eq!(stats.buffer_overflow, 2);
+    assert_eq!(stats.buffer_len, 3);
+}
+
+#[test]
+fn test_drop_newest_keeps_earliest_entries() {
+    let logger = buffering_logger(3, BufferOverflowPolicy::DropNewest);
+    log_numbered(&logger, 5);
+
+    let buffered = logger.buffered_entries();
+    assert_eq!(messages(&buffered), ["Message 0", "Message 1", "Message 2"]);
+    assert_eq!(logger.stats().buffer_overflow, 2);
+}
+
+#[test]
+fn test_spill_to_stderr_evicts_oldest() {
+    let logger = buffering_logger(2, BufferOverflowPolicy::SpillToStderr);
+    log_numbered(&logger, 4);
+
+    let buffered = logger.buffered_entries();
+    assert_eq!(messages(&buffered), ["Message 2", "Message 3"]);
+    assert_eq!(logger.stats().buffer_overflow, 2);
+}
+
+#[test]
+fn test_buffered_entries_are_replayed_to_first_transport() {
+    let logger = buffering_logger(10, BufferOverflowPolicy::DropOldest);
+    log_numbered(&logger, 3);
+    assert_eq!(logger.buffered_entries().len(), 3);
+
+    let transport = MockTransport::new();
+    logger.add_transport(transport.clone());
+    logger.flush().unwrap();
+
+    assert_eq!(transport.log_count(), 3);
+    assert!(logger.buffered_entries().is_empty());
+}
+
+#[test]
+fn test_drain_buffer_takes_entries() {
+    let logger = buffering_logger(10, BufferOverflowPolicy::DropOldest);
+    log_numbered(&logger, 3);
+
+    let drained = logger.drain_buffer();
+    assert_eq!(messages(&drained), ["Message 0", "Message 1", "Message 2"]);
+    assert_eq!(logger.stats().buffer_len, 0);
+
+    // Drained entries are not replayed once a transport is added
+    let transport = MockTransport::new();
+    logger.add_transport(transport.clone());
+    logger.flush().unwrap();
+    assert_eq!(transport.log_count(), 0);
+}
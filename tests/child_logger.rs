@@ -0,0 +1,115 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use winston::{log, meta, Logger};
+
+winston::create_log_methods!(info, warn);
+
+fn passthrough_logger(transport: &MockTransport) -> Logger {
+    Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build()
+}
+
+#[test]
+fn test_child_adds_meta_to_every_entry() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let child = logger.child([("request_id", "abc-123")]);
+    child.log(LogInfo::new("info", "First"));
+    log!(child, warn, "Second", attempt = 2);
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 2);
+    for entry in &logs {
+        assert_eq!(entry.meta["request_id"], "abc-123");
+    }
+    assert_eq!(logs[1].meta["attempt"], 2);
+}
+
+#[test]
+fn test_entry_meta_wins_over_child_meta() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let child = logger.child([("user", "default")]);
+    log!(child, info, "Override", meta!(user = "explicit"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs[0].meta["user"], "explicit");
+}
+
+#[test]
+fn test_nested_children_merge_meta() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let service = logger.child([("service", "api"), ("layer", "outer")]);
+    let request = service.child([("request_id", "r-1"), ("layer", "inner")]);
+    request.log(LogInfo::new("info", "Nested"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs[0].meta["service"], "api");
+    assert_eq!(logs[0].meta["request_id"], "r-1");
+    assert_eq!(logs[0].meta["layer"], "inner");
+    assert_eq!(service.meta().len(), 2);
+}
+
+#[test]
+fn test_child_with_generated_log_methods() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let child = logger.child([("tenant", "acme")]);
+    child.info("From method", None);
+    child.warn("With metadata", Some(meta!(code = 7)));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 2);
+    assert!(logs.iter().all(|entry| entry.meta["tenant"] == "acme"));
+    assert_eq!(logs[1].meta["code"], 7);
+}
+
+#[test]
+fn test_child_moves_into_spawned_thread() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let handles: Vec<_> = (0..3)
+        .map(|worker| {
+            let child = logger.child([("worker", worker)]);
+            std::thread::spawn(move || log!(child, info, "From thread"))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    logger.flush().unwrap();
+
+    let mut workers: Vec<_> = transport
+        .get_logs()
+        .iter()
+        .map(|entry| entry.meta["worker"].as_i64().unwrap())
+        .collect();
+    workers.sort_unstable();
+    assert_eq!(workers, [0, 1, 2]);
+}
+
+#[test]
+fn test_dropping_child_keeps_parent_open() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    drop(logger.child([("scope", "short")]));
+    logger.log(LogInfo::new("info", "Still open"));
+    logger.flush().unwrap();
+
+    assert_eq!(transport.log_count(), 1);
+}
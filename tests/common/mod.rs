@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use logform::LogInfo;
 use std::sync::{Arc, Mutex};
 use std::thread;
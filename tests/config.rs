@@ -0,0 +1,209 @@
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::path::PathBuf;
+use std::time::Duration;
+use winston::{
+    BackpressureStrategy, ConfigFormat, ConfigLoader, Diagnostics, Logger, LoggerBuilder,
+    LoggerOptions, LoggerTransport,
+};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("winston-config-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn load_toml(loader: &ConfigLoader, source: &str) -> Result<LoggerOptions, String> {
+    loader.load_str(source, ConfigFormat::Toml)
+}
+
+#[test]
+fn test_toml_sets_options() {
+    let options = load_toml(
+        &ConfigLoader::new(),
+        r#"
+        level = "debug"
+        channel_capacity = 64
+        backpressure_strategy = { type = "block_timeout", timeout_ms = 5 }
+        format = ["timestamp", "json"]
+
+        [levels]
+        error = 0
+        info = 1
+        debug = 2
+
+        [[transports]]
+        type = "stdout"
+        level = "info"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(options.level.as_deref(), Some("debug"));
+    assert_eq!(options.channel_capacity, Some(64));
+    assert!(matches!(
+        options.backpressure_strategy,
+        Some(BackpressureStrategy::BlockTimeout(timeout)) if timeout == Duration::from_millis(5)
+    ));
+    let transports = options.transports.as_ref().unwrap();
+    assert_eq!(transports.len(), 1);
+    assert_eq!(
+        transports[0].1.get_level().map(String::as_str),
+        Some("info")
+    );
+}
+
+#[test]
+fn test_json_file_with_file_transport() {
+    let log_path = temp_path("json.log");
+    let config_path = temp_path("config.json");
+    std::fs::write(
+        &config_path,
+        serde_json::json!({
+            "level": "warn",
+            "format": "simple",
+            "backpressure_strategy": "drop_oldest",
+            "transports": [{ "type": "file", "path": log_path }],
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let logger = LoggerBuilder::from_config_file(&config_path)
+        .unwrap()
+        .diagnostics(Diagnostics::silent())
+        .build();
+    logger.log(LogInfo::new("info", "Skipped"));
+    logger.log(LogInfo::new("warn", "Disk almost full"));
+    logger.close();
+
+    let written = std::fs::read_to_string(&log_path).unwrap();
+    assert!(written.contains("warn: Disk almost full"), "{}", written);
+    assert!(!written.contains("Skipped"));
+
+    let _ = std::fs::remove_file(log_path);
+    let _ = std::fs::remove_file(config_path);
+}
+
+#[test]
+fn test_registered_transport_factory() {
+    let transport = MockTransport::new();
+    let registered = transport.clone();
+    let loader = ConfigLoader::new().register_transport("mock", move |config| {
+        assert_eq!(config.require_str("name")?, "audit");
+        Ok(LoggerTransport::new(registered.clone()))
+    });
+
+    let options = load_toml(
+        &loader,
+        r#"
+        [[transports]]
+        type = "mock"
+        name = "audit"
+        level = "error"
+        format = "passthrough"
+        "#,
+    )
+    .unwrap();
+
+    let logger = Logger::new(Some(options));
+    logger.log(LogInfo::new("info", "Ignored"));
+    logger.log(LogInfo::new("error", "Recorded"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "Recorded");
+}
+
+#[test]
+fn test_errors_name_the_key() {
+    let loader = ConfigLoader::new();
+    let cases = [
+        ("verbosity = 1", "verbosity: unknown key"),
+        ("level = 3", "level: expected a string"),
+        (
+            "[[transports]]\ntype = \"stdout\"\n[[transports]]\ntype = \"kafka\"",
+            "transports[1].type: unknown transport \"kafka\" (registered: file, stderr, stdout)",
+        ),
+        (
+            "[[transports]]\ntype = \"file\"",
+            "transports[0].path: missing required key",
+        ),
+        (
+            "format = [\"json\", \"xml\"]",
+            "format[1]: unknown format \"xml\"",
+        ),
+        (
+            "backpressure_strategy = { type = \"sample\" }",
+            "backpressure_strategy.every: missing required key",
+        ),
+        (
+            "[levels]\nerror = 300",
+            "levels.error: expected a severity from 0 to 255",
+        ),
+    ];
+
+    for (source, expected) in cases {
+        assert_eq!(load_toml(&loader, source).unwrap_err(), expected);
+    }
+}
+
+#[test]
+fn test_levels_and_transport_keys_are_checked() {
+    let loader = ConfigLoader::new();
+    let cases = [
+        ("level = \"warnn\"", "level: unknown level \"warnn\""),
+        (
+            "[[transports]]\ntype = \"stdout\"\nlevel = \"verbose\"",
+            "transports[0].level: unknown level \"verbose\"",
+        ),
+        (
+            "level = \"debug\"\n[levels]\nerror = 0\nnotice = 1",
+            "level: unknown level \"debug\"",
+        ),
+        (
+            "[[transports]]\ntype = \"stdout\"\npath = \"app.log\"",
+            "transports[0].path: unknown key",
+        ),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(load_toml(&loader, source).unwrap_err(), expected);
+    }
+
+    // Custom levels apply wherever `levels` appears in the file
+    let options = load_toml(
+        &loader,
+        "level = \"notice\"\n[levels]\nerror = 0\nnotice = 1\n[[transports]]\ntype = \"stderr\"\nlevel = \"error\"",
+    )
+    .unwrap();
+    assert_eq!(options.level.as_deref(), Some("notice"));
+}
+
+#[test]
+fn test_file_errors_name_the_path() {
+    let config_path = temp_path("broken.toml");
+    std::fs::write(&config_path, "channel_capacity = \"large\"").unwrap();
+
+    let err = LoggerOptions::from_config_file(&config_path).unwrap_err();
+    assert_eq!(
+        err,
+        format!(
+            "{}: channel_capacity: expected a non-negative integer",
+            config_path.display()
+        )
+    );
+
+    let err = LoggerOptions::from_config_file("logging.yaml").unwrap_err();
+    assert!(
+        err.starts_with("logging.yaml: unsupported config format"),
+        "{}",
+        err
+    );
+
+    let _ = std::fs::remove_file(config_path);
+}
@@ -0,0 +1,296 @@
+#![cfg(feature = "config")]
+
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::path::PathBuf;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
+use winston::{ConfigLoader, Diagnostic, DiagnosticKind, Diagnostics, Logger, LoggerTransport};
+
+const POLL: Duration = Duration::from_millis(10);
+
+fn temp_config(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "winston-watch-{}-{}.toml",
+        std::process::id(),
+        name
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(POLL);
+    }
+    false
+}
+
+fn mock_loader(transports: &[(&'static str, MockTransport)]) -> ConfigLoader {
+    transports
+        .iter()
+        .fold(ConfigLoader::new(), |loader, (name, transport)| {
+            let transport = transport.clone();
+            loader.register_transport(*name, move |_| Ok(LoggerTransport::new(transport.clone())))
+        })
+}
+
+#[test]
+fn test_level_change_is_applied() {
+    let path = temp_config("level", "level = \"info\"");
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+    logger
+        .watch_config_with(&path, ConfigLoader::new(), POLL)
+        .unwrap();
+
+    logger.log(LogInfo::new("debug", "Hidden"));
+    std::fs::write(&path, "level = \"debug\"").unwrap();
+
+    assert!(wait_until(|| {
+        logger.log(LogInfo::new("debug", "Visible"));
+        logger.flush().unwrap();
+        transport.has_message("Visible")
+    }));
+    assert!(!transport.has_message("Hidden"));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_transport_change_keeps_queued_entries() {
+    let path = temp_config("transports", "[[transports]]\ntype = \"old\"");
+    let old = MockTransport::new();
+    let new = MockTransport::new();
+    let loader = mock_loader(&[("old", old.clone()), ("new", new.clone())]);
+    let logger = Logger::new(Some(
+        loader
+            .load_file(&path)
+            .unwrap()
+            .format(logform::passthrough()),
+    ));
+    logger.watch_config_with(&path, loader, POLL).unwrap();
+
+    for i in 0..50 {
+        logger.log(LogInfo::new("info", format!("Before {}", i)));
+    }
+    std::fs::write(&path, "[[transports]]\ntype = \"new\"").unwrap();
+    assert!(wait_until(|| {
+        logger.log(LogInfo::new("info", "After"));
+        logger.flush().unwrap();
+        new.log_count() > 0
+    }));
+
+    let before = |t: &MockTransport| {
+        t.get_logs()
+            .iter()
+            .filter(|e| e.message.starts_with("Before"))
+            .count()
+    };
+    assert_eq!(before(&old) + before(&new), 50);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_invalid_change_keeps_config_and_reports() {
+    let path = temp_config("invalid", "level = \"warn\"");
+    let reported = Arc::new(Mutex::new(Vec::<Diagnostic>::new()));
+    let sink = Arc::clone(&reported);
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("warn")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .diagnostics(Diagnostics::callback(move |d| {
+            sink.lock().unwrap().push(d.clone())
+        }))
+        .build();
+    logger
+        .watch_config_with(&path, ConfigLoader::new(), POLL)
+        .unwrap();
+
+    std::fs::write(&path, "level = [\"debug\"").unwrap();
+    assert!(wait_until(|| !reported.lock().unwrap().is_empty()));
+
+    let diagnostic = reported.lock().unwrap()[0].clone();
+    assert_eq!(diagnostic.kind, DiagnosticKind::Config);
+    assert!(
+        diagnostic.message.contains(&path.display().to_string()),
+        "{}",
+        diagnostic.message
+    );
+
+    logger.log(LogInfo::new("info", "Still filtered"));
+    logger.log(LogInfo::new("warn", "Still written"));
+    logger.flush().unwrap();
+    assert!(!transport.has_message("Still filtered"));
+    assert!(transport.has_message("Still written"));
+
+    // A fixed file is picked up again
+    std::fs::write(&path, "level = \"info\"").unwrap();
+    assert!(wait_until(|| {
+        logger.log(LogInfo::new("info", "Now written"));
+        logger.flush().unwrap();
+        transport.has_message("Now written")
+    }));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_unchanged_transports_are_kept() {
+    let path = temp_config(
+        "unchanged-transports",
+        "level = \"info\"\n[[transports]]\ntype = \"counted\"",
+    );
+    let transport = MockTransport::new();
+    let built = Arc::new(AtomicUsize::new(0));
+    let loader = {
+        let transport = transport.clone();
+        let built = Arc::clone(&built);
+        ConfigLoader::new().register_transport("counted", move |_| {
+            built.fetch_add(1, Ordering::SeqCst);
+            Ok(LoggerTransport::new(transport.clone()))
+        })
+    };
+    let logger = Logger::new(Some(
+        loader
+            .load_file(&path)
+            .unwrap()
+            .format(logform::passthrough()),
+    ));
+    logger.watch_config_with(&path, loader, POLL).unwrap();
+    let builds = built.load(Ordering::SeqCst);
+
+    std::fs::write(
+        &path,
+        "level = \"debug\"\n[[transports]]\ntype = \"counted\"",
+    )
+    .unwrap();
+    assert!(wait_until(|| {
+        logger.log(LogInfo::new("debug", "Visible"));
+        logger.flush().unwrap();
+        transport.has_message("Visible")
+    }));
+    assert_eq!(built.load(Ordering::SeqCst), builds);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_unknown_level_keeps_config_and_reports() {
+    let path = temp_config("unknown-level", "level = \"warn\"");
+    let reported = Arc::new(Mutex::new(Vec::<Diagnostic>::new()));
+    let sink = Arc::clone(&reported);
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("warn")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .diagnostics(Diagnostics::callback(move |d| {
+            sink.lock().unwrap().push(d.clone())
+        }))
+        .build();
+    logger
+        .watch_config_with(&path, ConfigLoader::new(), POLL)
+        .unwrap();
+
+    std::fs::write(&path, "level = \"verbose\"").unwrap();
+    assert!(wait_until(|| !reported.lock().unwrap().is_empty()));
+    let diagnostic = reported.lock().unwrap()[0].clone();
+    assert_eq!(diagnostic.kind, DiagnosticKind::Config);
+    assert!(
+        diagnostic
+            .message
+            .contains("level: unknown level \"verbose\""),
+        "{}",
+        diagnostic.message
+    );
+
+    logger.log(LogInfo::new("info", "Still filtered"));
+    logger.log(LogInfo::new("warn", "Still written"));
+    logger.flush().unwrap();
+    assert!(!transport.has_message("Still filtered"));
+    assert!(transport.has_message("Still written"));
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_watch_requires_valid_file() {
+    let logger = Logger::builder().diagnostics(Diagnostics::silent()).build();
+
+    let path = temp_config("bad-start", "level = 1");
+    let err = logger.watch_config(&path).unwrap_err();
+    assert_eq!(err, format!("{}: level: expected a string", path.display()));
+
+    assert!(logger.watch_config("missing.toml").is_err());
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn test_removed_key_keeps_value_and_reports() {
+    let path = temp_config("removed", "level = \"debug\"\nchannel_capacity = 64");
+    let reported = Arc::new(Mutex::new(Vec::<Diagnostic>::new()));
+    let sink = Arc::clone(&reported);
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("debug")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .diagnostics(Diagnostics::callback(move |d| {
+            sink.lock().unwrap().push(d.clone())
+        }))
+        .build();
+    logger
+        .watch_config_with(&path, ConfigLoader::new(), POLL)
+        .unwrap();
+
+    std::fs::write(&path, "channel_capacity = 64").unwrap();
+    assert!(wait_until(|| !reported.lock().unwrap().is_empty()));
+
+    let diagnostic = reported.lock().unwrap()[0].clone();
+    assert_eq!(diagnostic.kind, DiagnosticKind::Config);
+    assert!(
+        diagnostic.message.contains("level"),
+        "{}",
+        diagnostic.message
+    );
+    assert!(
+        !diagnostic.message.contains("channel_capacity"),
+        "{}",
+        diagnostic.message
+    );
+
+    // The level stays in effect
+    logger.log(LogInfo::new("debug", "Still written"));
+    logger.flush().unwrap();
+    assert!(transport.has_message("Still written"));
+
+    // Reported once, and the key can be added back
+    std::fs::write(&path, "level = \"info\"\nchannel_capacity = 64").unwrap();
+    assert!(wait_until(|| {
+        transport.clear_logs();
+        logger.log(LogInfo::new("debug", "Hidden"));
+        logger.log(LogInfo::new("info", "Marker"));
+        logger.flush().unwrap();
+        transport.has_message("Marker") && !transport.has_message("Hidden")
+    }));
+    assert_eq!(reported.lock().unwrap().len(), 1);
+
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use winston::{BackpressureStrategy, DiagnosticKind, Diagnostics, Logger, LoggerTransport};
+
+#[test]
+fn test_slow_transport_does_not_stall_others() {
+    let slow = MockTransport::with_delay(Duration::from_millis(50));
+    let fast = MockTransport::new();
+
+    let logger = Logger::builder()
+        .transport(
+            LoggerTransport::new(slow.clone())
+                .with_dedicated_worker(100, BackpressureStrategy::Block),
+        )
+        .transport(fast.clone())
+        .build();
+
+    let start = Instant::now();
+    for i in 0..5 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+
+    // The fast transport is served by the logger's worker while the slow one
+    // is still working through its own queue
+    while fast.log_count() < 5 {
+        assert!(start.elapsed() < Duration::from_millis(200));
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(slow.log_count() < 5);
+
+    logger.flush().unwrap();
+    assert_eq!(slow.log_count(), 5);
+}
+
+#[test]
+fn test_dedicated_worker_drop_current_only_affects_its_transport() {
+    let slow = MockTransport::with_delay(Duration::from_millis(20));
+    let fast = MockTransport::new();
+
+    let logger = Logger::builder()
+        .transport(
+            LoggerTransport::new(slow.clone())
+                .with_dedicated_worker(1, BackpressureStrategy::DropCurrent),
+        )
+        .transport(fast.clone())
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(fast.log_count(), 10);
+    assert!(slow.log_count() < 10);
+    assert!(slow.log_count() >= 1);
+}
+
+#[test]
+fn test_dedicated_worker_drop_oldest_keeps_newest() {
+    let slow = MockTransport::with_delay(Duration::from_millis(20));
+
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(
+            LoggerTransport::new(slow.clone())
+                .with_dedicated_worker(2, BackpressureStrategy::DropOldest),
+        )
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert!(slow.log_count() < 10);
+    assert!(slow.has_message("Message 9"));
+}
+
+#[test]
+fn test_close_waits_for_dedicated_queues() {
+    let slow = MockTransport::with_delay(Duration::from_millis(10));
+
+    let logger = Logger::builder()
+        .transport(
+            LoggerTransport::new(slow.clone())
+                .with_dedicated_worker(100, BackpressureStrategy::Block),
+        )
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.close();
+
+    assert_eq!(slow.log_count(), 10);
+}
+
+#[test]
+fn test_query_goes_through_dedicated_worker() {
+    let transport = MockTransport::new();
+
+    let logger = Logger::builder()
+        .format(logform::timestamp())
+        .transport(
+            LoggerTransport::new(transport.clone())
+                .with_dedicated_worker(10, BackpressureStrategy::Block),
+        )
+        .build();
+
+    logger.log(LogInfo::new("info", "Queued"));
+    logger.flush().unwrap();
+
+    let results = logger.query(&winston::LogQuery::new()).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].message, "Queued");
+}
+
+#[test]
+fn test_dedicated_worker_drops_reach_logger_stats_and_diagnostics() {
+    let slow = MockTransport::with_delay(Duration::from_millis(20));
+    let collected = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&collected);
+
+    let logger = Logger::builder()
+        .diagnostics(Diagnostics::callback(move |d| {
+            sink.lock().unwrap().push(d.kind)
+        }))
+        .transport(
+            LoggerTransport::new(slow.clone())
+                .with_dedicated_worker(1, BackpressureStrategy::DropCurrent),
+        )
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let dropped = 10 - slow.log_count() as u64;
+    assert!(dropped > 0);
+    assert_eq!(logger.stats().dropped_current, dropped);
+    let collected = collected.lock().unwrap();
+    assert_eq!(collected.len() as u64, dropped);
+    assert!(collected.iter().all(|kind| *kind == DiagnosticKind::Drop));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::{MockConfig, MockTransport};
+use logform::LogInfo;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use winston::{
+    BackpressureStrategy, Diagnostic, DiagnosticKind, DiagnosticSeverity, Diagnostics, Logger,
+};
+
+fn collecting() -> (Diagnostics, Arc<Mutex<Vec<Diagnostic>>>) {
+    let collected = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&collected);
+    let diagnostics = Diagnostics::callback(move |d| sink.lock().unwrap().push(d.clone()));
+    (diagnostics, collected)
+}
+
+fn overloaded_logger(diagnostics: Diagnostics) -> Logger {
+    Logger::builder()
+        .channel_capacity(1)
+        .backpressure_strategy(BackpressureStrategy::DropCurrent)
+        .transport(MockTransport::with_delay(Duration::from_millis(20)))
+        .diagnostics(diagnostics)
+        .build()
+}
+
+#[test]
+fn test_callback_receives_drop_diagnostics() {
+    let (diagnostics, collected) = collecting();
+    let logger = overloaded_logger(diagnostics);
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let collected = collected.lock().unwrap();
+    assert_eq!(collected.len() as u64, logger.stats().dropped_current);
+    assert!(collected.iter().all(|d| d.kind == DiagnosticKind::Drop
+        && d.severity == DiagnosticSeverity::Warning
+        && d.message.contains("full channel")));
+}
+
+#[test]
+fn test_no_transport_diagnostic() {
+    let (diagnostics, collected) = collecting();
+    let logger = Logger::builder().diagnostics(diagnostics).build();
+
+    logger.log(LogInfo::new("info", "Nobody listening"));
+    logger.flush().unwrap();
+
+    let collected = collected.lock().unwrap();
+    assert_eq!(collected.len(), 1);
+    assert_eq!(collected[0].kind, DiagnosticKind::NoTransport);
+}
+
+#[test]
+fn test_rate_limit_reports_suppressed_count() {
+    let (diagnostics, collected) = collecting();
+    let logger = overloaded_logger(diagnostics.with_rate_limit(2, Duration::from_millis(100)));
+
+    for i in 0..20 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+    let dropped = logger.stats().dropped_current;
+    assert!(dropped > 2);
+    assert_eq!(collected.lock().unwrap().len(), 2);
+
+    // The first drop in the next window carries the suppressed count
+    std::thread::sleep(Duration::from_millis(120));
+    for i in 0..5 {
+        logger.log(LogInfo::new("info", format!("Later {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let collected = collected.lock().unwrap();
+    assert!(collected.len() >= 3);
+    assert_eq!(collected[2].suppressed, dropped - 2);
+    assert!(collected[2]
+        .to_string()
+        .contains(&format!("{} similar messages suppressed", dropped - 2)));
+}
+
+#[test]
+fn test_min_severity_filters_warnings() {
+    let (diagnostics, collected) = collecting();
+    let logger = Logger::builder()
+        .diagnostics(diagnostics.with_min_severity(DiagnosticSeverity::Error))
+        .build();
+
+    logger.log(LogInfo::new("info", "Buffered without transports"));
+    logger.flush().unwrap();
+    assert!(collected.lock().unwrap().is_empty());
+
+    logger.add_transport(MockTransport::with_config(MockConfig {
+        should_fail_flush: true,
+        ..Default::default()
+    }));
+    logger.close();
+
+    let collected = collected.lock().unwrap();
+    assert!(!collected.is_empty());
+    assert!(collected
+        .iter()
+        .all(|d| d.kind == DiagnosticKind::FlushError && d.severity == DiagnosticSeverity::Error));
+}
+
+#[test]
+fn test_silent_diagnostics_still_count_drops() {
+    let logger = overloaded_logger(Diagnostics::silent());
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert!(logger.stats().dropped_current > 0);
+}
+
+#[test]
+fn test_default_sink_is_rate_limited() {
+    let default = format!("{:?}", Diagnostics::default());
+    assert!(default.contains("sink: \"stderr\""));
+    assert!(default.contains("rate_limit: Some((10, 10s))"));
+
+    // An explicit stderr sink is only limited when asked
+    assert!(format!("{:?}", Diagnostics::stderr()).contains("rate_limit: None"));
+}
@@ -2,7 +2,8 @@ mod common;
 
 use common::{MockConfig, MockTransport};
 use logform::LogInfo;
-use winston::Logger;
+use std::sync::{Arc, Mutex};
+use winston::{Logger, TransportHandle};
 
 #[test]
 fn test_transport_log_failure_does_not_crash() {
@@ -36,13 +37,56 @@ fn test_transport_flush_failure() {
     };
     let transport = MockTransport::with_config(config);
 
-    let logger = Logger::builder().transport(transport).build();
+    let logger = Logger::builder().transport(transport.clone()).build();
 
     logger.log(LogInfo::new("info", "Test"));
 
-    // Flush should still complete even if transport fails
+    // Flush still completes, but reports the failure
     let result = logger.flush();
-    assert!(result.is_ok());
+    assert_eq!(transport.log_count(), 1);
+    let error = result.unwrap_err();
+    assert!(error.contains("Mock flush failure"), "{}", error);
+}
+
+#[test]
+fn test_flush_error_names_only_failing_transports() {
+    let failing = MockTransport::with_config(MockConfig {
+        should_fail_flush: true,
+        ..Default::default()
+    });
+    let working = MockTransport::new();
+
+    let logger = Logger::builder().transport(working.clone()).build();
+    let failing_handle = logger.add_transport(failing.clone());
+
+    logger.log(LogInfo::new("info", "Test"));
+
+    let error = logger.flush().unwrap_err();
+    assert!(error.contains("1 transport(s)"), "{}", error);
+    assert!(error.contains(&failing_handle.to_string()), "{}", error);
+    assert_eq!(working.log_count(), 1);
+}
+
+#[test]
+fn test_on_error_receives_handle_and_error() {
+    let failures: Arc<Mutex<Vec<(TransportHandle, String)>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&failures);
+
+    let logger = Logger::builder()
+        .on_error(move |handle, error| sink.lock().unwrap().push((handle, error.to_string())))
+        .build();
+    let handle = logger.add_transport(MockTransport::with_config(MockConfig {
+        should_fail_flush: true,
+        ..Default::default()
+    }));
+
+    logger.log(LogInfo::new("info", "Test"));
+    assert!(logger.flush().is_err());
+
+    let failures = failures.lock().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, handle);
+    assert_eq!(failures[0].1, "Mock flush failure");
 }
 
 #[test]
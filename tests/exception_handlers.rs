@@ -0,0 +1,176 @@
+mod common;
+
+use common::MockTransport;
+use serial_test::serial;
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+use winston::Logger;
+
+fn panic_in_named_thread(name: &str, message: &'static str) {
+    let result = thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || panic!("{}", message))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+}
+
+#[test]
+#[serial]
+fn test_exception_handlers_receive_panics() {
+    let handler = MockTransport::new();
+    let regular = MockTransport::new();
+
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(regular.clone())
+        .exception_handler(handler.clone())
+        .build();
+
+    panic_in_named_thread("worker-7", "disk on fire");
+
+    let logs = handler.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "error");
+    assert!(logs[0].message.contains("disk on fire"));
+    assert_eq!(logs[0].meta["thread"], "worker-7");
+    assert_eq!(logs[0].meta["panic"], true);
+    assert!(logs[0].meta.contains_key("location"));
+    assert!(logs[0].meta.contains_key("backtrace"));
+
+    // Dedicated handlers replace the regular transports for panics
+    logger.flush().unwrap();
+    assert_eq!(regular.log_count(), 0);
+
+    logger.close();
+}
+
+#[test]
+#[serial]
+fn test_handle_panics_falls_back_to_transports() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    logger.handle_panics();
+    panic_in_named_thread("fallback", "unexpected state");
+
+    assert_eq!(transport.log_count(), 1);
+    assert!(transport.has_message("unexpected state"));
+
+    logger.close();
+}
+
+#[test]
+#[serial]
+fn test_panic_hook_chains_and_can_be_removed() {
+    let previous_calls = Arc::new(AtomicUsize::new(0));
+    let calls = Arc::clone(&previous_calls);
+    let original = panic::take_hook();
+    panic::set_hook(Box::new(move |_| {
+        calls.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    let handler = MockTransport::new();
+    let logger = Logger::builder().exception_handler(handler.clone()).build();
+
+    panic_in_named_thread("chained", "first");
+    assert_eq!(handler.log_count(), 1);
+    assert_eq!(previous_calls.load(Ordering::SeqCst), 1);
+
+    logger.unhandle_panics();
+    panic_in_named_thread("removed", "second");
+    assert_eq!(handler.log_count(), 1);
+    assert_eq!(previous_calls.load(Ordering::SeqCst), 2);
+
+    drop(logger);
+    panic::set_hook(original);
+}
+
+#[test]
+#[serial]
+fn test_dropping_logger_keeps_hooks_installed_later() {
+    let original = panic::take_hook();
+    let logger = Logger::builder()
+        .exception_handler(MockTransport::new())
+        .build();
+
+    let later_calls = Arc::new(AtomicUsize::new(0));
+    let calls = Arc::clone(&later_calls);
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        previous(info);
+    }));
+
+    drop(logger);
+    panic_in_named_thread("after-drop", "still hooked");
+    assert_eq!(later_calls.load(Ordering::SeqCst), 1);
+
+    panic::set_hook(original);
+}
+
+#[test]
+#[serial]
+fn test_configure_installs_hook_for_new_handlers() {
+    let handler = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .default_meta([("service", "api")])
+        .build();
+
+    logger.configure(Some(
+        winston::LoggerOptions::new()
+            .format(logform::passthrough())
+            .exception_handler(handler.clone()),
+    ));
+    panic_in_named_thread("configured", "late handler");
+
+    let logs = handler.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].message.contains("late handler"));
+    assert_eq!(logs[0].meta["service"], "api");
+
+    logger.close();
+}
+
+#[test]
+#[serial]
+fn test_unhandle_panics_restores_previous_hook() {
+    type Hook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send>;
+    let address = |hook: &Hook| &**hook as *const _ as *const () as usize;
+
+    let original = panic::take_hook();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let hook: Hook = Box::new(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    let installed = address(&hook);
+    panic::set_hook(hook);
+
+    for _ in 0..3 {
+        let logger = Logger::builder()
+            .exception_handler(MockTransport::new())
+            .build();
+        logger.unhandle_panics();
+        drop(logger);
+    }
+
+    // No disabled wrappers are left behind
+    let current = panic::take_hook();
+    assert_eq!(address(&current), installed);
+    panic::set_hook(current);
+    panic_in_named_thread("restored", "plain hook");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    panic::set_hook(original);
+}
@@ -0,0 +1,266 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+use winston::{
+    transports::{FailoverTransport, LogQuery, Transport},
+    Logger, LoggerTransport,
+};
+
+/// A transport whose flush fails while `down` is set, like an unreachable collector.
+#[derive(Clone)]
+struct FlakyTransport {
+    inner: MockTransport,
+    down: Arc<AtomicBool>,
+}
+
+impl FlakyTransport {
+    fn new() -> Self {
+        Self {
+            inner: MockTransport::new(),
+            down: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn set_down(&self, down: bool) {
+        self.down.store(down, Ordering::SeqCst);
+    }
+}
+
+impl Transport<LogInfo> for FlakyTransport {
+    fn log(&self, info: LogInfo) {
+        self.inner.log(info);
+    }
+
+    fn flush(&self) -> Result<(), String> {
+        if self.down.load(Ordering::SeqCst) {
+            Err("collector unreachable".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn query(&self, options: &LogQuery) -> Result<Vec<LogInfo>, String> {
+        self.inner.query(options)
+    }
+}
+
+fn log_and_flush(logger: &Logger, message: &str) {
+    logger.log(LogInfo::new("info", message));
+    let _ = logger.flush();
+}
+
+#[test]
+fn test_writes_to_primary_while_healthy() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+
+    let logger = Logger::builder()
+        .transport(FailoverTransport::new(primary.clone()).with_secondary(backup.clone()))
+        .build();
+
+    log_and_flush(&logger, "First");
+    log_and_flush(&logger, "Second");
+
+    assert_eq!(primary.inner.log_count(), 2);
+    assert_eq!(backup.log_count(), 0);
+}
+
+#[test]
+fn test_fails_over_after_repeated_failures() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+    primary.set_down(true);
+
+    let failover = Arc::new(
+        FailoverTransport::new(primary.clone())
+            .with_secondary(backup.clone())
+            .with_max_failures(2),
+    );
+
+    failover.log(LogInfo::new("info", "One"));
+    assert!(failover.flush().is_err());
+    assert!(failover.is_healthy(0));
+
+    failover.log(LogInfo::new("info", "Two"));
+    assert!(failover.flush().is_err());
+    assert!(!failover.is_healthy(0));
+    assert_eq!(failover.active_index(), Some(1));
+
+    failover.log(LogInfo::new("info", "Three"));
+    assert!(failover.flush().is_ok());
+
+    // The entries the primary never confirmed are written to the backup too
+    assert_eq!(primary.inner.log_count(), 2);
+    let messages: Vec<_> = backup.get_logs().into_iter().map(|e| e.message).collect();
+    assert_eq!(messages, ["One", "Two", "Three"]);
+}
+
+#[test]
+fn test_probes_primary_after_cooldown() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+    primary.set_down(true);
+
+    let failover = FailoverTransport::new(primary.clone())
+        .with_secondary(backup.clone())
+        .with_max_failures(1)
+        .with_cooldown(Duration::from_millis(50));
+
+    failover.log(LogInfo::new("info", "Lost"));
+    let _ = failover.flush();
+    assert_eq!(failover.active_index(), Some(1));
+
+    // A failed probe starts another cooldown
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(failover.active_index(), Some(0));
+    failover.log(LogInfo::new("info", "Probe"));
+    assert!(failover.flush().is_err());
+    assert_eq!(failover.active_index(), Some(1));
+
+    // A successful probe restores the primary
+    primary.set_down(false);
+    thread::sleep(Duration::from_millis(60));
+    failover.log(LogInfo::new("info", "Recovered"));
+    assert!(failover.flush().is_ok());
+    assert!(failover.is_healthy(0));
+    assert_eq!(failover.active_index(), Some(0));
+}
+
+#[test]
+fn test_works_with_logger_transport_options() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+    primary.set_down(true);
+
+    let logger = Logger::builder()
+        .transport(
+            LoggerTransport::new(
+                FailoverTransport::new(primary.clone())
+                    .with_secondary(backup.clone())
+                    .with_max_failures(1),
+            )
+            .with_level("warn")
+            .with_format(logform::passthrough()),
+        )
+        .build();
+
+    logger.log(LogInfo::new("info", "Below threshold"));
+    log_and_flush(&logger, "Also below threshold");
+    logger.log(LogInfo::new("error", "To primary"));
+    assert!(logger.flush().is_err());
+    logger.log(LogInfo::new("error", "To backup"));
+    logger.flush().unwrap();
+
+    assert_eq!(primary.inner.log_count(), 1);
+    let backup_logs = backup.get_logs();
+    assert_eq!(backup_logs.len(), 2);
+    assert_eq!(backup_logs[0].message, "To primary");
+    assert_eq!(backup_logs[1].message, "To backup");
+}
+
+#[test]
+fn test_query_merges_all_transports() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+    primary.set_down(true);
+
+    let logger = Logger::builder()
+        .format(logform::timestamp())
+        .transport(
+            FailoverTransport::new(primary.clone())
+                .with_secondary(backup.clone())
+                .with_max_failures(1),
+        )
+        .build();
+
+    log_and_flush(&logger, "Before failover");
+    log_and_flush(&logger, "After failover");
+
+    // The primary still holds the entry that was replayed to the backup
+    let results = logger.query(&LogQuery::new()).unwrap();
+    assert_eq!(results.len(), 3);
+}
+
+#[test]
+fn test_health_checks_fail_over_without_explicit_flushes() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+    primary.set_down(true);
+
+    let logger = Logger::builder()
+        .transport(
+            FailoverTransport::new(primary.clone())
+                .with_secondary(backup.clone())
+                .with_max_failures(2)
+                .with_check_every(3),
+        )
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    // Two failed checks, after the third and sixth entries, mark the primary
+    // unhealthy, and the six entries it took are replayed to the backup
+    assert_eq!(primary.inner.log_count(), 6);
+    assert_eq!(backup.log_count(), 10);
+}
+
+#[test]
+fn test_no_entries_lost_across_failover_and_failed_probe() {
+    let primary = FlakyTransport::new();
+    let backup = MockTransport::new();
+
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(
+            FailoverTransport::new(primary.clone())
+                .with_secondary(backup.clone())
+                .with_max_failures(1)
+                .with_cooldown(Duration::from_millis(50))
+                .with_check_every(4),
+        )
+        .build();
+
+    let mut sent = Vec::new();
+    let mut log = |message: String| {
+        logger.log(LogInfo::new("info", &message));
+        sent.push(message);
+    };
+
+    // Confirmed by a successful check while the primary is up
+    for i in 0..4 {
+        log(format!("Up {}", i));
+    }
+    logger.flush().unwrap();
+    primary.set_down(true);
+    for i in 0..6 {
+        log(format!("Down {}", i));
+    }
+    logger.flush().unwrap();
+
+    // The primary is probed after the cooldown, and the next check fails it again
+    thread::sleep(Duration::from_millis(60));
+    for i in 0..2 {
+        log(format!("Probe {}", i));
+    }
+    logger.flush().unwrap();
+    logger.close();
+
+    let confirmed: Vec<_> = primary.inner.get_logs()[..4]
+        .iter()
+        .chain(backup.get_logs().iter())
+        .map(|e| e.message.clone())
+        .collect();
+    assert_eq!(confirmed, sent);
+}
@@ -87,7 +87,7 @@ fn test_meta_macro_with_different_types() {
     let metadata = meta!(
         string_val = "text",
         int_val = 42,
-        float_val = 3.14,
+        float_val = 2.5,
         bool_val = true
     );
 
@@ -223,3 +223,224 @@ fn test_log_macro_with_global_and_meta_macro() {
 
     ////winston::close();
 }
+
+#[test]
+fn test_log_macro_skips_evaluation_for_disabled_levels() {
+    use std::cell::Cell;
+
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("info")
+        .transport(transport.clone())
+        .build();
+
+    let evaluations = Cell::new(0);
+    let expensive = |label: &str| {
+        evaluations.set(evaluations.get() + 1);
+        label.to_string()
+    };
+
+    log!(
+        logger,
+        debug,
+        expensive("message"),
+        detail = expensive("meta")
+    );
+    log!(
+        logger,
+        trace,
+        expensive("message"),
+        meta!(detail = expensive("meta"))
+    );
+    assert_eq!(evaluations.get(), 0);
+
+    log!(
+        logger,
+        info,
+        expensive("message"),
+        detail = expensive("meta")
+    );
+    logger.flush().unwrap();
+
+    assert_eq!(evaluations.get(), 2);
+    assert_eq!(transport.log_count(), 1);
+}
+
+#[test]
+fn test_log_macro_evaluates_logger_expression_once() {
+    use std::cell::Cell;
+
+    let transport = MockTransport::new();
+    let logger = Logger::builder().transport(transport.clone()).build();
+
+    let lookups = Cell::new(0);
+    let get_logger = || {
+        lookups.set(lookups.get() + 1);
+        &logger
+    };
+
+    log!(get_logger(), info, "Once");
+    logger.flush().unwrap();
+
+    assert_eq!(lookups.get(), 1);
+    assert_eq!(transport.log_count(), 1);
+}
+
+winston::create_level_macros!(info, warn);
+
+#[test]
+fn test_log_macro_interpolates_format_arguments() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    let id = 42;
+    let ip = "10.0.0.1";
+    log!(
+        logger,
+        info,
+        "user {} logged in from {ip}",
+        id,
+        session = "abc"
+    );
+    log!(
+        logger,
+        warn,
+        "{} retries left",
+        3,
+        meta!(component = "auth")
+    );
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs[0].message, "user 42 logged in from 10.0.0.1");
+    assert_eq!(logs[0].meta["session"], "abc");
+    assert_eq!(logs[1].message, "3 retries left");
+    assert_eq!(logs[1].meta["component"], "auth");
+}
+
+#[test]
+fn test_log_macro_named_arguments_fill_placeholders() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    let addr = "10.0.0.1";
+    log!(logger, info, "{ip} connected", ip = addr, port = 8080);
+    info!(logger, "{user} has {} items", 3, user = "alice");
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs[0].message, "10.0.0.1 connected");
+    assert_eq!(logs[0].meta["ip"], "10.0.0.1");
+    assert_eq!(logs[0].meta["port"], 8080);
+    assert_eq!(logs[1].message, "alice has 3 items");
+    assert_eq!(logs[1].meta["user"], "alice");
+}
+
+#[test]
+fn test_log_macro_defers_formatting_for_disabled_levels() {
+    use std::cell::Cell;
+    use std::fmt;
+
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl fmt::Display for Counted<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            write!(f, "counted")
+        }
+    }
+
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("info")
+        .transport(transport.clone())
+        .build();
+
+    let formatted = Cell::new(0);
+    let counted = Counted(&formatted);
+    log!(logger, debug, "value: {counted}");
+    assert_eq!(formatted.get(), 0);
+
+    log!(logger, info, "value: {counted}");
+    logger.flush().unwrap();
+    assert_eq!(formatted.get(), 1);
+}
+
+#[test]
+fn test_literal_message_with_meta_expression_and_format_arguments() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    let meta_vec = vec![("component", serde_json::json!("auth"))];
+    log!(logger, info, "Meta form {}", meta_vec.clone());
+    info!(logger, "Level macro meta form", meta_vec);
+    log!(logger, info, "Format form {} of {}", 1, 2);
+    info!(logger, "Level macro format form {}", 3, retries = 2);
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 4);
+    assert_eq!(logs[0].message, "Meta form {}");
+    assert_eq!(logs[0].meta["component"], "auth");
+    assert_eq!(logs[1].message, "Level macro meta form");
+    assert_eq!(logs[1].meta["component"], "auth");
+    assert_eq!(logs[2].message, "Format form 1 of 2");
+    assert_eq!(logs[3].message, "Level macro format form 3");
+    assert_eq!(logs[3].meta["retries"], 2);
+}
+
+#[test]
+fn test_level_macros_with_logger_and_format_arguments() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    let user = "alice";
+    info!(logger, "Plain message");
+    info!(logger, "user {user} has {} items", 3, cart = "open");
+    warn!(logger, "Quota low", meta!(remaining = 5));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].message, "Plain message");
+    assert_eq!(logs[1].message, "user alice has 3 items");
+    assert_eq!(logs[1].meta["cart"], "open");
+    assert_eq!(logs[2].level, "warn");
+    assert_eq!(logs[2].meta["remaining"], 5);
+}
+
+#[test]
+#[serial]
+fn test_level_macros_with_global_logger_format_arguments() {
+    let transport = MockTransport::new();
+
+    if !winston::is_initialized() {
+        winston::init(Logger::builder().format(logform::passthrough()).build());
+    } else {
+        winston::configure(Some(
+            winston::LoggerOptions::new().format(logform::passthrough()),
+        ));
+    }
+    winston::add_transport(transport.clone());
+
+    let attempt = 2;
+    info!("Global attempt {attempt}", source = "macro");
+    winston::flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "Global attempt 2");
+    assert_eq!(logs[0].meta["source"], "macro");
+}
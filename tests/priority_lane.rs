@@ -0,0 +1,107 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::time::Duration;
+use winston::{BackpressureStrategy, Diagnostics, Logger};
+
+fn overloaded_logger(strategy: BackpressureStrategy, transport: MockTransport) -> Logger {
+    Logger::builder()
+        .format(logform::passthrough())
+        .channel_capacity(1)
+        .backpressure_strategy(strategy)
+        .priority_level("error")
+        .transport(transport)
+        .diagnostics(Diagnostics::silent())
+        .build()
+}
+
+fn log_mixed(logger: &Logger, count: usize) {
+    for i in 0..count {
+        logger.log(LogInfo::new("info", format!("Info {}", i)));
+        if i % 5 == 0 {
+            logger.log(LogInfo::new("error", format!("Error {}", i)));
+        }
+    }
+}
+
+fn error_messages(transport: &MockTransport) -> Vec<String> {
+    transport
+        .get_logs()
+        .into_iter()
+        .filter(|e| e.level == "error")
+        .map(|e| e.message)
+        .collect()
+}
+
+#[test]
+fn test_drop_current_never_drops_priority_entries() {
+    let transport = MockTransport::with_delay(Duration::from_millis(5));
+    let logger = overloaded_logger(BackpressureStrategy::DropCurrent, transport.clone());
+
+    log_mixed(&logger, 30);
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.dropped_current > 0);
+    assert_eq!(stats.prioritized, 6);
+    assert_eq!(
+        error_messages(&transport),
+        ["Error 0", "Error 5", "Error 10", "Error 15", "Error 20", "Error 25"]
+    );
+}
+
+#[test]
+fn test_drop_oldest_never_drops_priority_entries() {
+    let transport = MockTransport::with_delay(Duration::from_millis(5));
+    let logger = overloaded_logger(BackpressureStrategy::DropOldest, transport.clone());
+
+    log_mixed(&logger, 30);
+    logger.flush().unwrap();
+
+    assert!(logger.stats().dropped_oldest > 0);
+    assert_eq!(error_messages(&transport).len(), 6);
+}
+
+#[test]
+fn test_more_severe_levels_use_priority_lane() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("trace")
+        .priority_level("warn")
+        .transport(transport.clone())
+        .build();
+
+    for level in ["error", "warn", "info", "debug"] {
+        logger.log(LogInfo::new(level, "Message"));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().prioritized, 2);
+    assert_eq!(transport.log_count(), 4);
+}
+
+#[test]
+fn test_close_delivers_priority_entries() {
+    let transport = MockTransport::with_delay(Duration::from_millis(2));
+    let logger = overloaded_logger(BackpressureStrategy::DropCurrent, transport.clone());
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("error", format!("Error {}", i)));
+    }
+    logger.close();
+
+    assert_eq!(error_messages(&transport).len(), 10);
+}
+
+#[test]
+fn test_no_priority_level_uses_channel() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder().transport(transport.clone()).build();
+
+    logger.log(LogInfo::new("error", "Message"));
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().prioritized, 0);
+    assert_eq!(transport.log_count(), 1);
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::{thread, time::Duration};
+use winston::Logger;
+
+fn passthrough_logger(transport: &MockTransport) -> Logger {
+    Logger::builder()
+        .level("debug")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build()
+}
+
+#[test]
+fn test_timer_done_logs_duration() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let timer = logger.start_timer().with_level("debug");
+    thread::sleep(Duration::from_millis(20));
+    timer.done("Slow operation");
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "debug");
+    assert_eq!(logs[0].message, "Slow operation");
+    assert!(logs[0].meta["duration_ms"].as_u64().unwrap() >= 20);
+}
+
+#[test]
+fn test_timer_logs_when_dropped() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    {
+        let _timer = logger.start_timer().with_message("Scope finished");
+    }
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "info");
+    assert_eq!(logs[0].message, "Scope finished");
+    assert!(logs[0].meta.contains_key("duration_ms"));
+}
+
+#[test]
+fn test_timer_done_with_entry() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    let timer = logger.start_timer();
+    timer.done_with(LogInfo::new("warn", "Request").with_meta("path", "/users"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "warn");
+    assert_eq!(logs[0].meta["path"], "/users");
+    assert!(logs[0].meta.contains_key("duration_ms"));
+}
+
+#[test]
+fn test_profile_logs_on_second_call() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    logger.profile("db-query");
+    thread::sleep(Duration::from_millis(10));
+    logger.flush().unwrap();
+    assert_eq!(transport.log_count(), 0);
+
+    logger.profile("db-query");
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "db-query");
+    assert!(logs[0].meta["duration_ms"].as_u64().unwrap() >= 10);
+
+    // A third call starts a new profile
+    logger.profile("db-query");
+    logger.flush().unwrap();
+    assert_eq!(transport.log_count(), 1);
+}
+
+#[test]
+fn test_profile_with_custom_entry() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+
+    logger.profile("cache-warmup");
+    logger.profile_with(
+        "cache-warmup",
+        LogInfo::new("debug", "Cache warmed").with_meta("entries", 128),
+    );
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs[0].level, "debug");
+    assert_eq!(logs[0].message, "Cache warmed");
+    assert_eq!(logs[0].meta["entries"], 128);
+}
+
+#[test]
+fn test_timer_moves_into_thread_and_works_on_children() {
+    let transport = MockTransport::new();
+    let logger = passthrough_logger(&transport);
+    let child = logger.child([("request_id", "abc-123")]);
+
+    let timer = logger.start_timer();
+    thread::spawn(move || timer.done("Background job"))
+        .join()
+        .unwrap();
+    child.start_timer().done("Child request");
+    child.profile("lookup");
+    child.profile("lookup");
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].message, "Background job");
+    assert!(!logs[0].meta.contains_key("request_id"));
+    assert_eq!(logs[1].message, "Child request");
+    assert_eq!(logs[1].meta["request_id"], "abc-123");
+    assert!(logs[1].meta.contains_key("duration_ms"));
+    assert_eq!(logs[2].message, "lookup");
+    assert_eq!(logs[2].meta["request_id"], "abc-123");
+}
+
+#[test]
+fn test_global_timer() {
+    let transport = MockTransport::new();
+    winston::init(passthrough_logger(&transport));
+
+    winston::start_timer().done("Global timer");
+    winston::profile("global-profile");
+    winston::profile("global-profile");
+    winston::flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].message, "Global timer");
+    assert_eq!(logs[1].message, "global-profile");
+    assert!(logs[1].meta.contains_key("duration_ms"));
+}
@@ -2,8 +2,8 @@ mod common;
 
 use common::{wait_for_logs, MockTransport};
 use logform::LogInfo;
-use std::sync::Arc;
-use winston::{log, BackpressureStrategy, Logger, LoggerOptions};
+use std::time::Duration;
+use winston::{log, BackpressureStrategy, Logger, LoggerOptions, RateLimiter};
 
 #[test]
 fn test_logger_builder_api() {
@@ -149,6 +149,39 @@ fn test_configure_updates_logger() {
     assert_eq!(transport.log_count(), 1);
 }
 
+#[test]
+fn test_clear_removes_options_configure_keeps() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level("info")
+        .transport(transport.clone())
+        .filter(|e| !e.message.starts_with("noisy"))
+        .rate_limit(RateLimiter::new().limit_level("info", 1, Duration::from_secs(60)))
+        .build();
+
+    // Unset options keep their current value
+    logger.configure(Some(
+        LoggerOptions::new()
+            .level("info")
+            .transport(transport.clone()),
+    ));
+    log!(logger, info, "noisy before");
+    log!(logger, info, "first");
+    log!(logger, info, "second");
+    wait_for_logs(&logger);
+    assert!(!transport.has_message("noisy before"));
+    assert!(transport.has_message("first"));
+    assert!(!transport.has_message("second"));
+
+    logger.clear_filter();
+    logger.clear_rate_limit();
+    log!(logger, info, "noisy after");
+    log!(logger, info, "third");
+    wait_for_logs(&logger);
+    assert!(transport.has_message("noisy after"));
+    assert!(transport.has_message("third"));
+}
+
 #[test]
 fn test_query_with_level_filter() {
     let transport = MockTransport::new();
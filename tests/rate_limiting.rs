@@ -0,0 +1,152 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use serde_json::Value;
+use std::time::Duration;
+use winston::{Logger, LoggerTransport, RateLimiter};
+
+fn limited_logger(limiter: RateLimiter, transport: MockTransport) -> Logger {
+    Logger::builder()
+        .level("debug")
+        .format(logform::passthrough())
+        .rate_limit(limiter)
+        .transport(transport)
+        .build()
+}
+
+fn repeated(entry: &LogInfo) -> Option<&Value> {
+    entry.meta.get("repeated")
+}
+
+#[test]
+fn test_repeats_collapse_into_one_entry() {
+    let transport = MockTransport::new();
+    let logger = limited_logger(
+        RateLimiter::new().suppress_repeats(Duration::from_secs(60)),
+        transport.clone(),
+    );
+
+    for _ in 0..100 {
+        logger.log(LogInfo::new("error", "Connection refused"));
+    }
+    logger.log(LogInfo::new("info", "Still serving requests"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs[0].message, "Connection refused");
+    assert_eq!(repeated(&logs[0]), None);
+    assert_eq!(logs[1].message, "Still serving requests");
+    assert_eq!(logs[2].message, "Connection refused");
+    assert_eq!(repeated(&logs[2]), Some(&Value::from(99)));
+    assert_eq!(logger.stats().suppressed_repeats, 99);
+}
+
+#[test]
+fn test_summary_written_when_window_closes() {
+    let transport = MockTransport::new();
+    let logger = limited_logger(
+        RateLimiter::new().suppress_repeats(Duration::from_millis(20)),
+        transport.clone(),
+    );
+
+    for _ in 0..5 {
+        logger.log(LogInfo::new("error", "Connection refused"));
+    }
+    std::thread::sleep(Duration::from_millis(30));
+    logger.log(LogInfo::new("error", "Connection refused"));
+    logger.flush().unwrap();
+
+    let counts: Vec<_> = transport
+        .get_logs()
+        .iter()
+        .map(|e| repeated(e).cloned())
+        .collect();
+    assert_eq!(counts, [None, Some(Value::from(4)), None]);
+}
+
+#[test]
+fn test_summary_written_while_idle() {
+    let transport = MockTransport::new();
+    let logger = limited_logger(
+        RateLimiter::new().suppress_repeats(Duration::from_millis(50)),
+        transport.clone(),
+    );
+
+    for _ in 0..10 {
+        logger.log(LogInfo::new("error", "Connection refused"));
+    }
+    // Nothing else is logged or flushed, so only the worker can write the summary
+    std::thread::sleep(Duration::from_millis(300));
+
+    let counts: Vec<_> = transport
+        .get_logs()
+        .iter()
+        .map(|e| repeated(e).cloned())
+        .collect();
+    assert_eq!(counts, [None, Some(Value::from(9))]);
+}
+
+#[test]
+fn test_level_token_bucket() {
+    let transport = MockTransport::new();
+    let logger = limited_logger(
+        RateLimiter::new().limit_level("debug", 5, Duration::from_secs(60)),
+        transport.clone(),
+    );
+
+    for i in 0..20 {
+        logger.log(LogInfo::new("debug", format!("Tick {}", i)));
+    }
+    for i in 0..3 {
+        logger.log(LogInfo::new("info", format!("Request {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.iter().filter(|e| e.level == "debug").count(), 5);
+    assert_eq!(logs.iter().filter(|e| e.level == "info").count(), 3);
+    assert_eq!(logger.stats().rate_limited, 15);
+}
+
+#[test]
+fn test_key_token_bucket() {
+    let transport = MockTransport::new();
+    let logger = limited_logger(
+        RateLimiter::new().limit_key("tenant", 2, Duration::from_secs(60)),
+        transport.clone(),
+    );
+
+    for tenant in ["acme", "acme", "acme", "globex", "globex", "globex"] {
+        logger.log(LogInfo::new("info", "Request").with_meta("tenant", tenant));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(transport.log_count(), 4);
+    assert_eq!(logger.stats().rate_limited, 2);
+}
+
+#[test]
+fn test_transport_rate_limit_only_affects_its_transport() {
+    let limited = MockTransport::new();
+    let unlimited = MockTransport::new();
+    let logger = Logger::builder()
+        .format(logform::passthrough())
+        .transport(
+            LoggerTransport::new(limited.clone())
+                .with_rate_limit(RateLimiter::new().suppress_repeats(Duration::from_secs(60))),
+        )
+        .transport(unlimited.clone())
+        .build();
+
+    for _ in 0..10 {
+        logger.log(LogInfo::new("error", "Disk full"));
+    }
+    logger.close();
+
+    assert_eq!(unlimited.log_count(), 10);
+    let logs = limited.get_logs();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(repeated(&logs[1]), Some(&Value::from(9)));
+}
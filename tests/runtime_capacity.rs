@@ -0,0 +1,240 @@
+mod common;
+
+use common::{cleanup_file, temp_log_file, MockTransport};
+use logform::LogInfo;
+use std::{sync::Arc, thread, time::Duration};
+use winston::{BackpressureStrategy, Diagnostics, Logger, LoggerOptions};
+
+fn slow_logger(
+    capacity: usize,
+    strategy: BackpressureStrategy,
+    transport: MockTransport,
+) -> Logger {
+    Logger::builder()
+        .format(logform::passthrough())
+        .channel_capacity(capacity)
+        .backpressure_strategy(strategy)
+        .transport(transport)
+        .diagnostics(Diagnostics::silent())
+        .build()
+}
+
+fn messages(transport: &MockTransport) -> Vec<String> {
+    transport
+        .get_logs()
+        .into_iter()
+        .map(|e| e.message)
+        .collect()
+}
+
+#[test]
+fn test_resize_keeps_queued_messages_in_order() {
+    let transport = MockTransport::with_delay(Duration::from_millis(5));
+    let logger = Arc::new(slow_logger(
+        2,
+        BackpressureStrategy::Block,
+        transport.clone(),
+    ));
+
+    let producer = {
+        let logger = Arc::clone(&logger);
+        thread::spawn(move || {
+            for i in 0..20 {
+                logger.log(LogInfo::new("info", format!("Message {}", i)));
+            }
+        })
+    };
+    thread::sleep(Duration::from_millis(10));
+    logger.set_channel_capacity(100);
+    producer.join().unwrap();
+
+    for i in 20..30 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let expected: Vec<String> = (0..30).map(|i| format!("Message {}", i)).collect();
+    assert_eq!(messages(&transport), expected);
+}
+
+#[test]
+fn test_loggers_do_not_wait_for_resize_of_full_channel() {
+    let transport = MockTransport::with_delay(Duration::from_millis(100));
+    let logger = Arc::new(slow_logger(
+        1,
+        BackpressureStrategy::DropCurrent,
+        transport.clone(),
+    ));
+
+    // One entry in the transport, one filling the channel
+    logger.log(LogInfo::new("info", "Message 0"));
+    thread::sleep(Duration::from_millis(20));
+    logger.log(LogInfo::new("info", "Message 1"));
+    let resize = {
+        let logger = Arc::clone(&logger);
+        thread::spawn(move || logger.set_channel_capacity(100))
+    };
+    thread::sleep(Duration::from_millis(10));
+
+    let start = std::time::Instant::now();
+    logger.log(LogInfo::new("info", "During resize"));
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    resize.join().unwrap();
+    logger.flush().unwrap();
+    assert!(transport.has_message("During resize"));
+}
+
+#[test]
+fn test_blocked_sender_does_not_hold_up_resize_or_other_loggers() {
+    let transport = MockTransport::with_delay(Duration::from_millis(100));
+    let logger = Arc::new(slow_logger(
+        1,
+        BackpressureStrategy::Block,
+        transport.clone(),
+    ));
+
+    // One entry in the transport, one filling the channel, one waiting for room
+    logger.log(LogInfo::new("info", "Message 0"));
+    thread::sleep(Duration::from_millis(20));
+    logger.log(LogInfo::new("info", "Message 1"));
+    let blocked = {
+        let logger = Arc::clone(&logger);
+        thread::spawn(move || logger.log(LogInfo::new("info", "Message 2")))
+    };
+    thread::sleep(Duration::from_millis(10));
+    let resize = {
+        let logger = Arc::clone(&logger);
+        thread::spawn(move || logger.set_channel_capacity(100))
+    };
+    thread::sleep(Duration::from_millis(10));
+
+    let start = std::time::Instant::now();
+    let _ = logger.stats();
+    logger.log(LogInfo::new("info", "During resize"));
+    assert!(start.elapsed() < Duration::from_millis(50));
+
+    blocked.join().unwrap();
+    resize.join().unwrap();
+    logger.flush().unwrap();
+    assert_eq!(
+        messages(&transport),
+        ["Message 0", "Message 1", "Message 2", "During resize"]
+    );
+}
+
+#[test]
+fn test_drop_oldest_keeps_flush_requests() {
+    let transport = MockTransport::with_delay(Duration::from_millis(1));
+    let logger = Arc::new(slow_logger(
+        2,
+        BackpressureStrategy::DropOldest,
+        transport.clone(),
+    ));
+
+    let flusher = {
+        let logger = Arc::clone(&logger);
+        thread::spawn(move || {
+            for _ in 0..20 {
+                logger.flush().unwrap();
+            }
+        })
+    };
+    for i in 0..500 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+
+    // Every flush is answered even though its request competed for slots with entries
+    flusher.join().unwrap();
+    logger.flush().unwrap();
+    assert!(logger.stats().dropped_oldest > 0);
+}
+
+#[test]
+fn test_shrinking_capacity_loses_nothing() {
+    let transport = MockTransport::with_delay(Duration::from_millis(1));
+    let logger = slow_logger(100, BackpressureStrategy::Block, transport.clone());
+
+    for i in 0..50 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.set_channel_capacity(1);
+    for i in 50..60 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(transport.log_count(), 60);
+}
+
+#[test]
+fn test_wider_channel_stops_drops() {
+    let transport = MockTransport::with_delay(Duration::from_millis(1));
+    let logger = slow_logger(1, BackpressureStrategy::DropCurrent, transport.clone());
+
+    logger.set_channel_capacity(1000);
+    for i in 0..50 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().dropped(), 0);
+    assert_eq!(transport.log_count(), 50);
+}
+
+#[test]
+fn test_set_backpressure_strategy() {
+    let transport = MockTransport::with_delay(Duration::from_millis(2));
+    let logger = slow_logger(1, BackpressureStrategy::DropCurrent, transport.clone());
+
+    logger.set_backpressure_strategy(BackpressureStrategy::Block);
+    for i in 0..20 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().dropped(), 0);
+    assert_eq!(transport.log_count(), 20);
+}
+
+#[test]
+fn test_configure_applies_capacity_and_strategy() {
+    let transport = MockTransport::with_delay(Duration::from_millis(1));
+    let logger = slow_logger(1, BackpressureStrategy::Block, transport.clone());
+
+    logger.configure(Some(
+        LoggerOptions::new()
+            .format(logform::passthrough())
+            .channel_capacity(500)
+            .backpressure_strategy(BackpressureStrategy::DropCurrent)
+            .transport(transport.clone()),
+    ));
+    for i in 0..50 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(logger.stats().dropped(), 0);
+    assert_eq!(transport.log_count(), 50);
+}
+
+#[test]
+fn test_switch_to_spill_to_file_at_runtime() {
+    let spool = temp_log_file();
+    let transport = MockTransport::with_delay(Duration::from_millis(2));
+    let logger = slow_logger(1, BackpressureStrategy::DropCurrent, transport.clone());
+
+    logger.set_backpressure_strategy(BackpressureStrategy::SpillToFile(spool.clone().into()));
+    for i in 0..20 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.spilled > 0);
+    assert_eq!(stats.dropped(), 0);
+    assert_eq!(transport.log_count(), 20);
+
+    logger.close();
+    cleanup_file(&spool);
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use serde_json::Value;
+use winston::{Logger, LoggerTransport, Sampler};
+
+fn sampled_logger(sampler: Sampler, transport: MockTransport) -> Logger {
+    Logger::builder()
+        .level("trace")
+        .format(logform::passthrough())
+        .sampling(sampler)
+        .transport(transport)
+        .build()
+}
+
+#[test]
+fn test_ratio_sampling_happens_before_the_channel() {
+    let transport = MockTransport::new();
+    let logger = sampled_logger(Sampler::new().keep_ratio("debug", 0.01), transport.clone());
+
+    for i in 0..1000 {
+        logger.log(LogInfo::new("debug", format!("Tick {}", i)));
+    }
+    logger.log(LogInfo::new("info", "Unsampled"));
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert_eq!(stats.sampled_out, 990);
+    assert_eq!(stats.accepted, 11);
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 11);
+    let debug: Vec<_> = logs.iter().filter(|e| e.level == "debug").collect();
+    assert!(debug
+        .iter()
+        .all(|e| e.meta.get("sample_rate") == Some(&Value::from(0.01))));
+    assert!(logs
+        .iter()
+        .find(|e| e.level == "info")
+        .is_some_and(|e| !e.meta.contains_key("sample_rate")));
+}
+
+#[test]
+fn test_first_then_every() {
+    let transport = MockTransport::new();
+    let logger = sampled_logger(
+        Sampler::new().keep_first_then_every("trace", 10, 5),
+        transport.clone(),
+    );
+
+    for i in 0..60 {
+        logger.log(LogInfo::new("trace", format!("Trace {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 20);
+    assert!(logs[..10]
+        .iter()
+        .all(|e| !e.meta.contains_key("sample_rate")));
+    assert!(logs[10..]
+        .iter()
+        .all(|e| e.meta.get("sample_rate") == Some(&Value::from(0.2))));
+}
+
+#[test]
+fn test_entries_with_key_are_always_kept() {
+    let transport = MockTransport::new();
+    let logger = sampled_logger(
+        Sampler::new()
+            .keep_ratio("debug", 0.0)
+            .always_keep_key("trace_id"),
+        transport.clone(),
+    );
+
+    logger.log(LogInfo::new("debug", "Dropped"));
+    logger.log(LogInfo::new("debug", "Kept").with_meta("trace_id", "abc"));
+    logger.flush().unwrap();
+
+    let logs = transport.get_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].message, "Kept");
+}
+
+#[test]
+fn test_transport_sampling() {
+    let sampled = MockTransport::new();
+    let full = MockTransport::new();
+    let logger = Logger::builder()
+        .level("debug")
+        .format(logform::passthrough())
+        .transport(
+            LoggerTransport::new(sampled.clone())
+                .with_sampling(Sampler::new().keep_ratio("debug", 0.1)),
+        )
+        .transport(full.clone())
+        .build();
+
+    for i in 0..100 {
+        logger.log(LogInfo::new("debug", format!("Tick {}", i)));
+    }
+    logger.flush().unwrap();
+
+    assert_eq!(full.log_count(), 100);
+    assert!(full.get_logs().iter().all(|e| e.meta.is_empty()));
+    assert_eq!(sampled.log_count(), 10);
+    assert_eq!(logger.stats().sampled_out, 90);
+}
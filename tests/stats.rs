@@ -0,0 +1,97 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::time::Duration;
+use winston::{BackpressureStrategy, Logger, LoggerTransport};
+
+#[test]
+fn test_stats_count_accepted_filtered_and_delivered() {
+    let all = MockTransport::new();
+    let errors = MockTransport::new();
+
+    let logger = Logger::builder().level("info").build();
+    let all_handle = logger.add_transport(all.clone());
+    let errors_handle =
+        logger.add_transport(LoggerTransport::new(errors.clone()).with_level("error"));
+
+    logger.log(LogInfo::new("info", "Info"));
+    logger.log(LogInfo::new("error", "Error"));
+    logger.log(LogInfo::new("debug", "Debug"));
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert_eq!(stats.accepted, 2);
+    assert_eq!(stats.filtered, 1);
+    assert_eq!(stats.delivered[&all_handle], 2);
+    assert_eq!(stats.delivered[&errors_handle], 1);
+    assert_eq!(stats.dropped(), 0);
+    assert_eq!(stats.channel_depth, 0);
+}
+
+#[test]
+fn test_stats_count_buffered_entries() {
+    let logger = Logger::builder().build();
+
+    logger.log(LogInfo::new("info", "Nobody listening"));
+    logger.log(LogInfo::new("info", "Still nobody"));
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert_eq!(stats.buffered, 2);
+    assert_eq!(stats.buffer_len, 2);
+
+    let transport = MockTransport::new();
+    let handle = logger.add_transport(transport.clone());
+    logger.log(LogInfo::new("info", "Drains the buffer"));
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert_eq!(stats.buffer_len, 0);
+    assert_eq!(stats.delivered[&handle], 3);
+}
+
+#[test]
+fn test_stats_count_drop_current() {
+    let transport = MockTransport::with_delay(Duration::from_millis(20));
+    let logger = Logger::builder()
+        .channel_capacity(1)
+        .backpressure_strategy(BackpressureStrategy::DropCurrent)
+        .transport(transport.clone())
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert_eq!(stats.accepted, 10);
+    assert!(stats.dropped_current > 0);
+    assert_eq!(stats.dropped_oldest, 0);
+    assert_eq!(
+        stats.dropped_current + transport.log_count() as u64,
+        10,
+        "every accepted entry is either delivered or dropped"
+    );
+}
+
+#[test]
+fn test_stats_count_drop_oldest() {
+    let transport = MockTransport::with_delay(Duration::from_millis(20));
+    let logger = Logger::builder()
+        .channel_capacity(1)
+        .backpressure_strategy(BackpressureStrategy::DropOldest)
+        .transport(transport.clone())
+        .build();
+
+    for i in 0..10 {
+        logger.log(LogInfo::new("info", format!("Message {}", i)));
+    }
+    logger.flush().unwrap();
+
+    let stats = logger.stats();
+    assert!(stats.dropped_oldest > 0);
+    assert_eq!(stats.dropped_current, 0);
+    assert_eq!(stats.dropped_oldest + transport.log_count() as u64, 10);
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use common::MockTransport;
+use logform::LogInfo;
+use std::sync::{Arc, Mutex};
+use winston::{Diagnostic, DiagnosticKind, Diagnostics, Logger, LoggerBuilder, LoggerTransport};
+
+fn from_target(level: &str, target: &str, message: &str) -> LogInfo {
+    LogInfo::new(level, message).with_meta("target", target)
+}
+
+fn messages(transport: &MockTransport) -> Vec<String> {
+    transport
+        .get_logs()
+        .into_iter()
+        .map(|e| e.message)
+        .collect()
+}
+
+fn directive_logger(spec: &str, transport: &MockTransport) -> Logger {
+    Logger::builder()
+        .level_directives(spec)
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build()
+}
+
+#[test]
+fn test_directives_set_global_and_target_levels() {
+    let transport = MockTransport::new();
+    let logger = directive_logger("warn,my_crate::db=debug,hyper=error", &transport);
+
+    logger.log(from_target("info", "my_crate::http", "http info"));
+    logger.log(from_target("warn", "my_crate::http", "http warn"));
+    logger.log(from_target("debug", "my_crate::db::pool", "pool debug"));
+    logger.log(from_target("trace", "my_crate::db", "db trace"));
+    logger.log(from_target("warn", "hyper::client", "hyper warn"));
+    logger.log(from_target("error", "hyper", "hyper error"));
+    logger.log(LogInfo::new("info", "untargeted info"));
+    logger.flush().unwrap();
+
+    assert_eq!(
+        messages(&transport),
+        ["http warn", "pool debug", "hyper error"]
+    );
+    assert_eq!(logger.stats().filtered, 4);
+}
+
+#[test]
+fn test_module_path_and_off() {
+    let transport = MockTransport::new();
+    let logger = directive_logger("info,my_crate::db=debug,noisy=off", &transport);
+
+    let entry = LogInfo::new("debug", "via module path")
+        .with_meta("target", "sql")
+        .with_meta("module_path", "my_crate::db");
+    logger.log(entry);
+    logger.log(from_target("error", "noisy::inner", "silenced"));
+    logger.flush().unwrap();
+
+    assert_eq!(messages(&transport), ["via module path"]);
+}
+
+#[test]
+fn test_bare_off_silences_logger() {
+    let transport = MockTransport::new();
+    let logger = Logger::builder()
+        .level_directives("off")
+        .format(logform::passthrough())
+        .transport(LoggerTransport::new(transport.clone()).with_level("info"))
+        .build();
+
+    logger.log(LogInfo::new("error", "untargeted error"));
+    logger.log(from_target("error", "my_crate", "targeted error"));
+    logger.flush().unwrap();
+
+    assert!(transport.get_logs().is_empty());
+    assert!(!logger.is_enabled("error"));
+}
+
+#[test]
+fn test_bare_off_keeps_target_levels() {
+    let transport = MockTransport::new();
+    let logger = directive_logger("off,my_crate=debug", &transport);
+
+    logger.log(LogInfo::new("error", "untargeted error"));
+    logger.log(from_target("error", "other", "other error"));
+    logger.log(from_target("debug", "my_crate::db", "crate debug"));
+    logger.log(from_target("trace", "my_crate", "crate trace"));
+    logger.flush().unwrap();
+
+    assert_eq!(messages(&transport), ["crate debug"]);
+    assert!(logger.is_enabled("debug"));
+    assert!(!logger.is_enabled("trace"));
+}
+
+#[test]
+fn test_bare_target_enables_every_level() {
+    let transport = MockTransport::new();
+    let logger = directive_logger("warn,my_app", &transport);
+
+    logger.log(from_target("trace", "my_app::jobs", "app trace"));
+    logger.log(from_target("info", "other", "other info"));
+    logger.log(from_target("warn", "other", "other warn"));
+    logger.flush().unwrap();
+
+    assert_eq!(messages(&transport), ["app trace", "other warn"]);
+}
+
+#[test]
+fn test_invalid_directives_go_to_builder_diagnostics() {
+    let reported = Arc::new(Mutex::new(Vec::<Diagnostic>::new()));
+    let sink = Arc::clone(&reported);
+    let logger = Logger::builder()
+        .level_directives("info,=debug,my_crate=bogus")
+        .diagnostics(Diagnostics::callback(move |d| {
+            sink.lock().unwrap().push(d.clone())
+        }))
+        .build();
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 2);
+    assert_eq!(reported[0].kind, DiagnosticKind::Config);
+    assert!(reported[0].message.contains("\"=debug\""));
+    assert!(reported[1].message.contains("\"my_crate=bogus\""));
+    drop(logger);
+}
+
+#[test]
+fn test_is_enabled_considers_target_levels() {
+    let transport = MockTransport::new();
+    let logger = directive_logger("info,my_crate=debug", &transport);
+
+    // A `debug` entry may be written if it comes from `my_crate`
+    assert!(logger.is_enabled("debug"));
+    assert!(!logger.is_enabled("trace"));
+}
+
+#[test]
+fn test_transport_levels_are_kept() {
+    let quiet = MockTransport::new();
+    let default = MockTransport::new();
+    let logger = Logger::builder()
+        .level("info")
+        .target_level("my_crate", "debug")
+        .format(logform::passthrough())
+        .transport(LoggerTransport::new(quiet.clone()).with_level("warn"))
+        .transport(default.clone())
+        .build();
+
+    logger.log(from_target("debug", "my_crate", "debug detail"));
+    logger.flush().unwrap();
+
+    assert!(quiet.get_logs().is_empty());
+    assert_eq!(messages(&default), ["debug detail"]);
+}
+
+#[test]
+fn test_from_env() {
+    std::env::set_var("WINSTON_LOG_TARGET_LEVELS_TEST", "error,app=info");
+    let transport = MockTransport::new();
+    let logger = LoggerBuilder::from_env("WINSTON_LOG_TARGET_LEVELS_TEST")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+
+    logger.log(from_target("info", "app::jobs", "job started"));
+    logger.log(from_target("info", "other", "other info"));
+    logger.flush().unwrap();
+    assert_eq!(messages(&transport), ["job started"]);
+
+    // Without the variable, the default level applies
+    let logger = LoggerBuilder::from_env("WINSTON_LOG_TARGET_LEVELS_UNSET")
+        .format(logform::passthrough())
+        .transport(transport.clone())
+        .build();
+    transport.clear_logs();
+    logger.log(LogInfo::new("info", "default info"));
+    logger.flush().unwrap();
+    assert_eq!(messages(&transport), ["default info"]);
+}